@@ -0,0 +1,67 @@
+//! Compare the throughput of recording from many threads into a `Mutex<{Enum}Counter>`,
+//! a `{Enum}StaticCounter` and a `{Enum}ShardedCounter`.
+//!
+//! Run it by `cargo bench --bench record`, the thread count can be set by the first argument.
+
+use std::{
+    hint::black_box,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use variant_counter::VariantCount;
+
+#[allow(dead_code)]
+#[derive(VariantCount)]
+enum Status {
+    Ok,
+    NotFound,
+    Error,
+}
+
+const RECORDS_PER_THREAD: usize = 1_000_000;
+
+/// Record `Status::Ok` from `threads` threads, returns the elapsed time.
+fn run(threads: usize, record: impl Fn(&Status) + Sync) -> Duration {
+    let start = Instant::now();
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                for _ in 0..RECORDS_PER_THREAD {
+                    record(black_box(&Status::Ok));
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn report(name: &str, threads: usize, elapsed: Duration, sum: usize) {
+    assert_eq!(sum, threads * RECORDS_PER_THREAD);
+    let nanos = elapsed.as_nanos() as f64 / sum as f64;
+    println!("{:<10} {:>10.2?} {:>8.2} ns/record", name, elapsed, nanos);
+}
+
+fn main() {
+    let threads = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(4)
+        });
+    println!("{} threads, {} records each", threads, RECORDS_PER_THREAD);
+
+    let mutex = Mutex::new(Status::counter());
+    let elapsed = run(threads, |status| mutex.lock().unwrap().record(status));
+    report("mutex", threads, elapsed, mutex.lock().unwrap().sum());
+
+    let atomic = StatusStaticCounter::new();
+    let elapsed = run(threads, |status| atomic.record(status));
+    report("atomic", threads, elapsed, atomic.snapshot().sum());
+
+    let sharded = StatusShardedCounter::<64>::with_shards();
+    let elapsed = run(threads, |status| sharded.record(status));
+    report("sharded", threads, elapsed, sharded.snapshot().sum());
+}
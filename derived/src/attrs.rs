@@ -1,23 +1,64 @@
 use std::collections::{BTreeMap, HashMap};
 
-use quote::quote_spanned;
-use syn::{DataEnum, Variant};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{spanned::Spanned, Data, DataEnum, DeriveInput, Variant};
+
+use crate::case::RenameRule;
 
 #[derive(Debug)]
 pub(crate) struct ParsedAttr {
     pub(crate) ignores: Vec<proc_macro2::Ident>,
+    // The ignored variants which are tracked by `#[counter(ignore, track)]`.
+    pub(crate) tracks: Vec<proc_macro2::Ident>,
     pub(crate) groups: BTreeMap<String, Vec<proc_macro2::Ident>>,
     pub(crate) weight: HashMap<proc_macro2::Ident, usize>,
+    pub(crate) expected: HashMap<proc_macro2::Ident, f64>,
+    // The display name of variants declared by `#[counter(rename = "...")]`.
+    pub(crate) renames: HashMap<proc_macro2::Ident, String>,
+    // The previous names of variants declared by `#[counter(alias = "...")]`.
+    pub(crate) aliases: HashMap<proc_macro2::Ident, Vec<String>>,
+    // The names of removed variants declared by `#[counter(retired = "...")]` on the enum.
+    pub(crate) retired: Vec<String>,
+    // The rule to rename all variants and groups declared by `#[counter(rename_all = "...")]`.
+    pub(crate) rename_all: Option<RenameRule>,
+    // The predicate of variants declared by `#[counter(when = "path::to::predicate")]`.
+    pub(crate) predicates: HashMap<proc_macro2::Ident, proc_macro2::TokenStream>,
+    // The variant to record into if the predicate fails, declared by `#[counter(fallback = "Variant")]`.
+    pub(crate) fallbacks: HashMap<proc_macro2::Ident, proc_macro2::Ident>,
+    // The path and the last segment of the foreign type declared by `#[counter(remote = "...")]`.
+    pub(crate) remote: Option<(proc_macro2::TokenStream, proc_macro2::Ident)>,
+    // The synthetic variant of the bucket which records unknown variants of a remote or `#[non_exhaustive]` enum,
+    // its name is `"__other"` or declared by `#[counter(other = "...")]`.
+    pub(crate) other: Option<proc_macro2::Ident>,
+    // Whether the remote enum is declared completely by `#[counter(exhaustive)]`, so it needs no other bucket.
+    pub(crate) exhaustive: bool,
+    // The extra derives of the counter struct declared by `#[counter(derive(...))]`.
+    pub(crate) derives: Vec<proc_macro2::TokenStream>,
     // Whether the user has declared a customize group.
     pub(crate) has_customized_group: bool,
 }
 
 impl ParsedAttr {
-    pub fn parse(data_enum: &DataEnum) -> Result<ParsedAttr, proc_macro2::TokenStream> {
+    pub fn parse(
+        input: &DeriveInput,
+        data_enum: &DataEnum,
+    ) -> Result<ParsedAttr, proc_macro2::TokenStream> {
         let mut parsed = ParsedAttr {
             ignores: vec![],
+            tracks: vec![],
             groups: BTreeMap::default(),
             weight: HashMap::default(),
+            expected: HashMap::default(),
+            renames: HashMap::default(),
+            aliases: HashMap::default(),
+            retired: vec![],
+            rename_all: None,
+            predicates: HashMap::default(),
+            fallbacks: HashMap::default(),
+            remote: None,
+            other: None,
+            exhaustive: false,
+            derives: vec![],
             has_customized_group: false,
         };
 
@@ -25,14 +66,67 @@ impl ParsedAttr {
             panic!("Empty enum is not supported.");
         }
 
+        parsed.parse_enum_attributes(&input.attrs)?;
         for variant in data_enum.variants.iter() {
             parsed.parse_variant_attributes(variant)?;
         }
 
+        // The variants of a remote or `#[non_exhaustive]` enum may be unknown to us.
+        if parsed.exhaustive && parsed.remote.is_none() {
+            return Err(quote! {
+                compile_error!("#[counter(exhaustive)] only works with remote enums: #[counter(remote = \"...\", exhaustive)]");
+            });
+        }
+        let is_non_exhaustive = matches!(input.data, Data::Enum(_))
+            && ((parsed.remote.is_some() && !parsed.exhaustive)
+                || input
+                    .attrs
+                    .iter()
+                    .any(|attr| attr.path.is_ident("non_exhaustive")));
+        match &parsed.other {
+            Some(other) if !is_non_exhaustive => {
+                return Err(quote_spanned! {other.span()=>
+                    compile_error!("#[counter(other)] only works with remote or #[non_exhaustive] enums.");
+                });
+            }
+            Some(_) => {}
+            None if is_non_exhaustive => {
+                parsed.set_other("__other".to_owned());
+            }
+            None => {}
+        }
+        if let Some(other) = parsed.other.clone() {
+            parsed.record_group(parsed.display_name(&other), other);
+        }
+
         if parsed.ignores.len() == data_enum.variants.len() {
             panic!("All variants were ignored, please check again.");
         }
 
+        if parsed.expected.values().sum::<f64>() > 1.0 + f64::EPSILON {
+            return Err(quote! {
+                compile_error!("The sum of `expected` proportions must not be greater than 1.");
+            });
+        }
+
+        for (variant, fallback) in parsed.fallbacks.iter() {
+            if !parsed.predicates.contains_key(variant) {
+                return Err(quote_spanned! {variant.span()=>
+                    compile_error!("#[counter(fallback)] only works with a predicate: #[counter(when = \"...\", fallback = \"...\")]");
+                });
+            }
+            let is_valid = fallback != variant
+                && data_enum
+                    .variants
+                    .iter()
+                    .any(|v| &v.ident == fallback && !parsed.is_ignored(v));
+            if !is_valid {
+                return Err(quote_spanned! {fallback.span()=>
+                    compile_error!("Invalid `fallback` value, expected another variant which is not ignored.");
+                });
+            }
+        }
+
         parsed.validate_legality();
         Ok(parsed)
     }
@@ -41,6 +135,118 @@ impl ParsedAttr {
         self.weight.values().any(|w| *w > 1)
     }
 
+    fn parse_enum_attributes(
+        &mut self,
+        attrs: &[syn::Attribute],
+    ) -> Result<(), proc_macro2::TokenStream> {
+        for attr in attrs.iter() {
+            match attr.parse_meta() {
+                Ok(syn::Meta::List(meta_list))
+                    if meta_list
+                        .path
+                        .get_ident()
+                        .filter(|&ident| ident == "counter")
+                        .is_some() =>
+                {
+                    for nested in meta_list.nested.iter() {
+                        match nested {
+                            syn::NestedMeta::Meta(syn::Meta::List(list))
+                                if list.path.is_ident("derive") =>
+                            {
+                                for derive in list.nested.iter() {
+                                    if let syn::NestedMeta::Meta(syn::Meta::Path(path)) = derive {
+                                        self.derives.push(quote! { #path });
+                                    } else {
+                                        return Err(quote_spanned! {list.path.span()=>
+                                            compile_error!("Invalid `derive` value, expected trait paths: #[counter(derive(Trait, ...))]");
+                                        });
+                                    }
+                                }
+                            }
+                            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                                if name_value.path.is_ident("rename_all") =>
+                            {
+                                let rule = match &name_value.lit {
+                                    syn::Lit::Str(str) => RenameRule::from_str(&str.value()),
+                                    _ => None,
+                                };
+                                if rule.is_none() {
+                                    let message = format!(
+                                        "Invalid `rename_all` value, expected one of: {}",
+                                        RenameRule::RULES
+                                            .iter()
+                                            .map(|(name, _)| format!("{:?}", name))
+                                            .collect::<Vec<_>>()
+                                            .join(", ")
+                                    );
+                                    return Err(quote_spanned! {name_value.lit.span()=>
+                                        compile_error!(#message);
+                                    });
+                                }
+                                self.rename_all = rule;
+                            }
+                            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                                if name_value.path.is_ident("retired") =>
+                            {
+                                if let syn::Lit::Str(str) = &name_value.lit {
+                                    self.retired.push(str.value());
+                                } else {
+                                    return Err(quote_spanned! {name_value.lit.span()=>
+                                        compile_error!("Invalid `retired` value, expected the name of a removed variant: #[counter(retired = \"...\")]");
+                                    });
+                                }
+                            }
+                            syn::NestedMeta::Meta(syn::Meta::Path(path))
+                                if path.is_ident("exhaustive") =>
+                            {
+                                self.exhaustive = true;
+                            }
+                            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                                if name_value.path.is_ident("other") =>
+                            {
+                                if let syn::Lit::Str(str) = &name_value.lit {
+                                    self.set_other(str.value());
+                                } else {
+                                    return Err(quote_spanned! {name_value.lit.span()=>
+                                        compile_error!("Invalid `other` value, expected a name: #[counter(other = \"...\")]");
+                                    });
+                                }
+                            }
+                            syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                                if name_value.path.is_ident("remote") =>
+                            {
+                                let path = match &name_value.lit {
+                                    syn::Lit::Str(str) => str.parse::<syn::Path>().ok(),
+                                    _ => None,
+                                };
+                                match path.as_ref().and_then(|path| path.segments.last()) {
+                                    Some(segment) if segment.arguments.is_empty() => {
+                                        self.remote =
+                                            Some((quote! { #path }, segment.ident.clone()));
+                                    }
+                                    _ => {
+                                        return Err(quote_spanned! {name_value.lit.span()=>
+                                            compile_error!("Invalid `remote` value, expected a type path without generics: #[counter(remote = \"path::to::Type\")]");
+                                        });
+                                    }
+                                }
+                            }
+                            syn::NestedMeta::Meta(meta) => {
+                                return Err(quote_spanned! {meta.path().span()=>
+                                    compile_error!("Unknown attribute.");
+                                });
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
     fn parse_variant_attributes(
         &mut self,
         variant: &Variant,
@@ -61,6 +267,9 @@ impl ParsedAttr {
                                     Some(ident) if ident == "ignore" => {
                                         self.ignores.push(variant.ident.clone());
                                     }
+                                    Some(ident) if ident == "track" => {
+                                        self.tracks.push(variant.ident.clone());
+                                    }
                                     Some(ident) => {
                                         return Err(quote_spanned! {ident.span()=>
                                             compile_error!("Unknown attribute");
@@ -78,6 +287,10 @@ impl ParsedAttr {
                                             if variant.ident != group_name {
                                                 self.has_customized_group = true;
                                             }
+                                            let group_name = match self.rename_all {
+                                                Some(rule) => rule.apply(&group_name),
+                                                None => group_name,
+                                            };
                                             self.record_group(group_name, variant.ident.clone());
                                         } else {
                                             return Err(quote_spanned! {name.span()=>
@@ -99,6 +312,77 @@ impl ParsedAttr {
                                             });
                                         }
                                     }
+                                    Some(name) if name == "rename" => {
+                                        if let syn::Lit::Str(str) = &name_value.lit {
+                                            self.renames.insert(variant.ident.clone(), str.value());
+                                        } else {
+                                            return Err(quote_spanned! {name.span()=>
+                                                compile_error!("Invalid `rename` value type: expected string type: #[counter(rename = `string type`)]");
+                                            });
+                                        }
+                                    }
+                                    Some(name) if name == "alias" => {
+                                        if let syn::Lit::Str(str) = &name_value.lit {
+                                            self.aliases
+                                                .entry(variant.ident.clone())
+                                                .or_default()
+                                                .push(str.value());
+                                        } else {
+                                            return Err(quote_spanned! {name_value.lit.span()=>
+                                                compile_error!("Invalid `alias` value, expected the previous name: #[counter(alias = \"...\")]");
+                                            });
+                                        }
+                                    }
+                                    Some(name) if name == "when" => {
+                                        let predicate = match &name_value.lit {
+                                            syn::Lit::Str(str) => str.parse::<syn::Path>().ok(),
+                                            _ => None,
+                                        };
+                                        if let Some(predicate) = predicate {
+                                            self.predicates.insert(
+                                                variant.ident.clone(),
+                                                quote! { #predicate },
+                                            );
+                                        } else {
+                                            return Err(quote_spanned! {name_value.lit.span()=>
+                                                compile_error!("Invalid `when` value, expected a function path: #[counter(when = \"path::to::predicate\")]");
+                                            });
+                                        }
+                                    }
+                                    Some(name) if name == "fallback" => {
+                                        let fallback = match &name_value.lit {
+                                            syn::Lit::Str(str) => {
+                                                str.parse::<proc_macro2::Ident>().ok()
+                                            }
+                                            _ => None,
+                                        };
+                                        if let Some(fallback) = fallback {
+                                            self.fallbacks.insert(variant.ident.clone(), fallback);
+                                        } else {
+                                            return Err(quote_spanned! {name_value.lit.span()=>
+                                                compile_error!("Invalid `fallback` value, expected a variant name: #[counter(fallback = \"Variant\")]");
+                                            });
+                                        }
+                                    }
+                                    Some(name) if name == "expected" => {
+                                        let value = match &name_value.lit {
+                                            syn::Lit::Float(value) => value.base10_parse::<f64>(),
+                                            syn::Lit::Int(value) => value.base10_parse::<f64>(),
+                                            _ => {
+                                                return Err(quote_spanned! {name.span()=>
+                                                    compile_error!("Invalid `expected` value type, expected float type: #[counter(expected = `float type`)]");
+                                                });
+                                            }
+                                        }
+                                        // A value which fails to parse is rejected by the range check.
+                                        .unwrap_or(f64::NAN);
+                                        if !(0.0..=1.0).contains(&value) {
+                                            return Err(quote_spanned! {name_value.lit.span()=>
+                                                compile_error!("Invalid `expected` value, expected a proportion between 0 and 1.");
+                                            });
+                                        }
+                                        self.expected.insert(variant.ident.clone(), value);
+                                    }
                                     Some(invalid_name) => {
                                         return Err(quote_spanned! {invalid_name.span()=>
                                             compile_error!("Unknown attribute.");
@@ -115,19 +399,31 @@ impl ParsedAttr {
             }
         }
 
+        if self.tracks.contains(&variant.ident) && !self.is_ignored(variant) {
+            return Err(quote_spanned! {variant.ident.span()=>
+                compile_error!("#[counter(track)] only works with ignored variants: #[counter(ignore, track)]");
+            });
+        }
+
         if self.is_ignored(variant) {
             return Ok(());
         }
 
         if self.index_group(&variant.ident).is_none() {
-            self.record_group(variant.ident.to_string(), variant.ident.clone());
+            self.record_group(self.display_name(&variant.ident), variant.ident.clone());
         }
 
         Ok(())
     }
 
+    fn set_other(&mut self, name: String) {
+        let other = format_ident!("__other");
+        self.renames.insert(other.clone(), name);
+        self.other = Some(other);
+    }
+
     fn record_group(&mut self, name: String, ident: proc_macro2::Ident) {
-        self.groups.entry(name).or_insert_with(Vec::new).push(ident);
+        self.groups.entry(name).or_default().push(ident);
     }
 
     fn record_weight(&mut self, value: usize, ident: proc_macro2::Ident) {
@@ -147,15 +443,44 @@ impl ParsedAttr {
             })
     }
 
+    /// The name used in aggregates, which is the renamed name,
+    /// or the variant name applied the `rename_all` rule.
+    pub(crate) fn display_name(&self, variant: &proc_macro2::Ident) -> String {
+        match (self.renames.get(variant), self.rename_all) {
+            (Some(name), _) => name.clone(),
+            (None, Some(rule)) => rule.apply(&variant.to_string()),
+            (None, None) => variant.to_string(),
+        }
+    }
+
+    /// The name to generate methods, which is the renamed name or the variant name.
+    /// The `rename_all` rule doesn't apply to method names.
+    pub(crate) fn method_name(&self, variant: &proc_macro2::Ident) -> String {
+        self.renames
+            .get(variant)
+            .cloned()
+            .unwrap_or_else(|| variant.to_string())
+    }
+
     pub(crate) fn is_ignored(&self, variant: &Variant) -> bool {
         self.ignores.contains(&variant.ident)
     }
 
+    pub(crate) fn is_tracked(&self, variant: &Variant) -> bool {
+        self.tracks.contains(&variant.ident)
+    }
+
     fn validate_legality(&self) {
         let conflict_names: Vec<_> = self
             .ignores
             .iter()
-            .filter(|ident| self.index_group(ident).is_some())
+            .filter(|ident| {
+                self.index_group(ident).is_some()
+                    || self.expected.contains_key(ident)
+                    || self.renames.contains_key(ident)
+                    || self.aliases.contains_key(ident)
+                    || self.predicates.contains_key(ident)
+            })
             .map(|ident| ident.to_string())
             .collect();
         if !conflict_names.is_empty() {
@@ -0,0 +1,129 @@
+/// Split an identifier into words, such as `HTTPServerError` into `HTTP`, `Server` and `Error`.
+///
+/// A word starts at an uppercase letter following a lowercase letter or a digit,
+/// or at the last uppercase letter of an acronym followed by a lowercase letter.
+/// Any non-alphanumeric character separates words.
+fn split_words(name: &str) -> Vec<String> {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut words = vec![];
+    let mut word = String::new();
+
+    for (index, c) in chars.iter().copied().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+
+        if c.is_uppercase() && !word.is_empty() {
+            let prev = chars[index - 1];
+            let next_is_lowercase = chars.get(index + 1).is_some_and(|c| c.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_numeric()
+                || (prev.is_uppercase() && next_is_lowercase)
+            {
+                words.push(std::mem::take(&mut word));
+            }
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Convert the name to `snake_case`, such as `ChromeOS` to `chrome_os`.
+pub(crate) fn to_snake_case(name: &str) -> String {
+    RenameRule::Snake.apply(name)
+}
+
+/// The rule of `#[counter(rename_all = "...")]`, in the style of serde.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    pub(crate) const RULES: [(&'static str, RenameRule); 8] = [
+        ("lowercase", RenameRule::Lower),
+        ("UPPERCASE", RenameRule::Upper),
+        ("PascalCase", RenameRule::Pascal),
+        ("camelCase", RenameRule::Camel),
+        ("snake_case", RenameRule::Snake),
+        ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+        ("kebab-case", RenameRule::Kebab),
+        ("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebab),
+    ];
+
+    pub(crate) fn from_str(rule: &str) -> Option<RenameRule> {
+        Self::RULES
+            .iter()
+            .find(|(name, _)| *name == rule)
+            .map(|(_, rule)| *rule)
+    }
+
+    /// Apply the rule to the name.
+    pub(crate) fn apply(self, name: &str) -> String {
+        let words = split_words(name);
+        let join = |separator: &str, upper: bool| {
+            words
+                .iter()
+                .map(|word| {
+                    if upper {
+                        word.to_uppercase()
+                    } else {
+                        word.to_lowercase()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(separator)
+        };
+        let capitalize = |word: &String| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| {
+                    first
+                        .to_uppercase()
+                        .chain(chars.flat_map(char::to_lowercase))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        match self {
+            RenameRule::Lower => name.to_lowercase(),
+            RenameRule::Upper => name.to_uppercase(),
+            RenameRule::Pascal => words
+                .iter()
+                .map(capitalize)
+                .collect::<Vec<String>>()
+                .concat(),
+            RenameRule::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(index, word)| {
+                    if index == 0 {
+                        word.to_lowercase()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect::<Vec<String>>()
+                .concat(),
+            RenameRule::Snake => join("_", false),
+            RenameRule::ScreamingSnake => join("_", true),
+            RenameRule::Kebab => join("-", false),
+            RenameRule::ScreamingKebab => join("-", true),
+        }
+    }
+}
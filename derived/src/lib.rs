@@ -2,11 +2,12 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use syn::{parse_macro_input, Data, DeriveInput};
+use syn::{parse_macro_input, Data, DataStruct, DeriveInput, Fields};
 
 use crate::{attrs::ParsedAttr, parsed::ParsedEnum};
 
 mod attrs;
+mod case;
 mod parsed;
 
 /// `VariantCount` derived procedure macro.
@@ -14,44 +15,311 @@ mod parsed;
 pub fn derive_variant_count(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    if let Data::Enum(data_enum) = &input.data {
-        let parsed_attr = match ParsedAttr::parse(data_enum) {
-            Ok(parsed_attr) => parsed_attr,
-            Err(error) => return error.into(),
-        };
-
-        let parsed = ParsedEnum::parse(&input, data_enum, &parsed_attr);
-
-        let mut quotes = vec![derive_impl(&input, &parsed)];
+    let parsed_result = match &input.data {
+        Data::Enum(data_enum) => ParsedAttr::parse(&input, data_enum).and_then(|parsed_attr| {
+            ParsedEnum::parse(&input, data_enum, &parsed_attr).map(|parsed| (parsed_attr, parsed))
+        }),
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => ParsedAttr::parse(&input, &parsed::field_variants(fields)).and_then(|parsed_attr| {
+            ParsedEnum::parse_struct(&input, fields, &parsed_attr)
+                .map(|parsed| (parsed_attr, parsed))
+        }),
+        _ => panic!("VariantCount only works on Enums and structs with named fields"),
+    };
+    let (parsed_attr, parsed) = match parsed_result {
+        Ok(parsed) => parsed,
+        Err(error) => return error.into(),
+    };
 
-        if parsed_attr.has_customized_weight() {
-            quotes.push(derive_weighted_impl(&input, &parsed));
-        }
+    let mut quotes = vec![
+        derive_impl(&input, &parsed),
+        derive_proportions_impl(&input, &parsed),
+        derive_diff_impl(&input, &parsed),
+        derive_static_impl(&input, &parsed),
+        derive_sharded_impl(&input, &parsed),
+        derive_sampled_impl(&input, &parsed),
+    ];
 
-        TokenStream::from(quote! {
-          #(#quotes)*
-        })
-    } else {
-        panic!("VariantCount only works on Enums");
+    if parsed_attr.has_customized_weight() {
+        quotes.push(derive_weighted_impl(&input, &parsed));
     }
+
+    TokenStream::from(quote! {
+      #(#quotes)*
+    })
 }
 
 fn derive_impl(input: &DeriveInput, parsed: &ParsedEnum) -> proc_macro2::TokenStream {
     let name = &input.ident;
+    let target = &parsed.target;
     let vis = &input.vis;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let variant_count = parsed.variant_count;
     let variant_len = parsed.variant_len;
     let match_arm_quotes = &parsed.match_arm_quotes;
+    let name_arm_quotes = &parsed.name_arm_quotes;
+    let weights = &parsed.weights;
+    let alias_quotes = &parsed.alias_quotes;
+    let alias_len = alias_quotes.len();
+    let retired_names = &parsed.retired_names;
+    let retired_len = retired_names.len();
     let aggregate_quotes = &parsed.aggregate_quotes;
-    let counter_struct = format_ident!("{}Counter", name);
+    let variant_names = &parsed.variant_names;
+    let variant_groups = &parsed.variant_groups;
+    let group_names = &parsed.group_names;
+    let group_len = group_names.len();
+    let expected_proportions = &parsed.expected_proportions;
+    let counter_struct = format_ident!("{}Counter", parsed.type_name);
+    let display_group_names = display_group_names(parsed, &counter_struct);
+    let ignored_len = parsed.ignored_names.len();
+    let ignored_names = &parsed.ignored_names;
+    let slot_len = variant_len + ignored_len;
+    let (ignored_to_slots, ignored_from_slots) = if ignored_len > 0 {
+        (
+            quote! { slots[#variant_len..].copy_from_slice(&self.ignored); },
+            quote! { counter.ignored.copy_from_slice(&slots[#variant_len..]); },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+    let (slot_quote, ignored_field, ignored_new, ignored_reset, ignored_fns) = if ignored_len > 0 {
+        (
+            quote! {
+                match index {
+                    Some(index) if index < #variant_len => {
+                        Some((Self::VARIANT_NAMES[index], &mut self.frequency[index]))
+                    }
+                    Some(index) => Some((
+                        [#(#ignored_names),*][index - #variant_len],
+                        &mut self.ignored[index - #variant_len],
+                    )),
+                    None => None,
+                }
+            },
+            quote! {
+                /// An array store the frequency of each ignored variant tracked by `#[counter(ignore, track)]`.
+                ignored: [usize; #ignored_len],
+            },
+            quote! { ignored: [0; #ignored_len], },
+            quote! { self.ignored = [0; #ignored_len]; },
+            quote! {
+                /// Get the total count of the ignored variants tracked by `#[counter(ignore, track)]`.
+                /// It is not included in `sum()` or any statistics.
+                #[inline]
+                #vis fn ignored_count(&self) -> usize {
+                    self.ignored.iter().sum()
+                }
+
+                /// Aggregate the count of each tracked ignored variant to a HashMap.
+                #[cfg(feature = "std")]
+                #vis fn ignored_aggregate(&self) -> std::collections::HashMap<&'static str, usize> {
+                    IntoIterator::into_iter([#(#ignored_names),*])
+                        .zip(self.ignored)
+                        .collect()
+                }
+
+                /// Aggregate the count of each tracked ignored variant to an array.
+                #[cfg(not(feature = "std"))]
+                #vis fn ignored_aggregate(&self) -> [(&'static str, usize); #ignored_len] {
+                    let mut aggregate = [("", 0); #ignored_len];
+                    for (entry, pair) in aggregate
+                        .iter_mut()
+                        .zip(IntoIterator::into_iter([#(#ignored_names),*]).zip(self.ignored))
+                    {
+                        *entry = pair;
+                    }
+                    aggregate
+                }
+            },
+        )
+    } else {
+        (
+            quote! { index.map(move |index| (Self::VARIANT_NAMES[index], &mut self.frequency[index])) },
+            quote! {},
+            quote! {},
+            quote! {},
+            quote! {},
+        )
+    };
+    let other_fns = match parsed.other_index {
+        Some(other_index) => quote! {
+            /// Get the count of unknown variants of the remote or `#[non_exhaustive]` enum,
+            /// which is included in `sum()` and the aggregates as the other bucket.
+            #[inline]
+            #vis const fn other_count(&self) -> usize {
+                self.frequency[#other_index]
+            }
+        },
+        None => quote! {},
+    };
+    let record_fns = match &parsed.flag_quotes {
+        None => quote! {
+            /// Get the index of the target variant, the indexes of tracked ignored variants
+            /// follow the frequency array. It is `None` if the variant is ignored.
+            fn index #impl_generics (target: &#target #ty_generics) -> Option<usize> #where_clause {
+                match target {
+                    #(#match_arm_quotes,)*
+                }
+            }
+
+            /// Get the index of each slot to record the target.
+            #[allow(dead_code)]
+            fn indices #impl_generics (target: &#target #ty_generics) -> impl Iterator<Item = usize> #where_clause {
+                Self::index(target).into_iter()
+            }
+
+            /// Get the name and the slot which stores the frequency of the target variant,
+            /// it is `None` if the variant is ignored.
+            fn slot #impl_generics (&mut self, target: &#target #ty_generics) -> Option<(&'static str, &mut usize)> #where_clause {
+                let index = Self::index(target);
+                #slot_quote
+            }
+
+            /// Record a variant. It has no effect if you record an ignored variant.
+            #vis fn record #impl_generics (&mut self, target: &#target #ty_generics) #where_clause {
+                if let Some((_, slot)) = self.slot(target) {
+                    *slot = slot.saturating_add(1);
+                }
+            }
+
+            /// Erase a record of the target variant, it saturates at zero.
+            /// It has no effect if you erase an ignored variant which is not tracked by `#[counter(ignore, track)]`.
+            #[inline]
+            #vis fn erase #impl_generics (&mut self, target: &#target #ty_generics) #where_clause {
+                self.erase_n(target, 1);
+            }
+
+            /// Erase `n` records of the target variant, it saturates at zero.
+            /// It has no effect if you erase an ignored variant which is not tracked by `#[counter(ignore, track)]`.
+            #vis fn erase_n #impl_generics (&mut self, target: &#target #ty_generics, n: usize) #where_clause {
+                if let Some((_, slot)) = self.slot(target) {
+                    *slot = slot.saturating_sub(n);
+                }
+            }
+
+            /// Erase a record of the target variant, or return an error without erasing
+            /// if the variant has no record. It always succeeds if you erase an ignored variant
+            /// which is not tracked by `#[counter(ignore, track)]`.
+            #[inline]
+            #vis fn try_erase #impl_generics (&mut self, target: &#target #ty_generics) -> Result<(), variant_counter::UnderflowError> #where_clause {
+                self.try_erase_n(target, 1)
+            }
+
+            /// Erase `n` records of the target variant, or return an error without erasing
+            /// if the variant has less than `n` records. It always succeeds if you erase an ignored variant
+            /// which is not tracked by `#[counter(ignore, track)]`.
+            #vis fn try_erase_n #impl_generics (&mut self, target: &#target #ty_generics, n: usize) -> Result<(), variant_counter::UnderflowError> #where_clause {
+                match self.slot(target) {
+                    Some((name, slot)) if *slot < n => Err(variant_counter::UnderflowError {
+                        name,
+                        count: *slot,
+                        requested: n,
+                    }),
+                    Some((_, slot)) => {
+                        *slot -= n;
+                        Ok(())
+                    }
+                    None => Ok(()),
+                }
+            }
+
+            /// Discard the record of the target variant, or of its fallback variant
+            /// if it fails the predicate of `#[counter(when = ..., fallback = ...)]`.
+            /// It has no effect if you discard an ignored variant which is not tracked by `#[counter(ignore, track)]`.
+            #vis fn discard #impl_generics (&mut self, target: &#target #ty_generics) #where_clause {
+                if let Some((_, slot)) = self.slot(target) {
+                    *slot = 0;
+                }
+            }
+        },
+        Some(flag_quotes) => quote! {
+            /// Get the index of each field which is set in the target.
+            fn flags #impl_generics (target: &#target #ty_generics) -> [Option<usize>; #variant_len] #where_clause {
+                [#(#flag_quotes),*]
+            }
+
+            /// Get the index of each slot to record the target.
+            #[allow(dead_code)]
+            fn indices #impl_generics (target: &#target #ty_generics) -> impl Iterator<Item = usize> #where_clause {
+                IntoIterator::into_iter(Self::flags(target)).flatten()
+            }
+
+            /// Record each field which is set, that is `true` or `Some`.
+            /// It has no effect on ignored fields.
+            #vis fn record #impl_generics (&mut self, target: &#target #ty_generics) #where_clause {
+                for index in IntoIterator::into_iter(Self::flags(target)).flatten() {
+                    self.frequency[index] = self.frequency[index].saturating_add(1);
+                }
+            }
+
+            /// Erase a record of each field which is set in the target, it saturates at zero.
+            /// It has no effect on ignored fields.
+            #[inline]
+            #vis fn erase #impl_generics (&mut self, target: &#target #ty_generics) #where_clause {
+                self.erase_n(target, 1);
+            }
+
+            /// Erase `n` records of each field which is set in the target, it saturates at zero.
+            /// It has no effect on ignored fields.
+            #vis fn erase_n #impl_generics (&mut self, target: &#target #ty_generics, n: usize) #where_clause {
+                for index in IntoIterator::into_iter(Self::flags(target)).flatten() {
+                    self.frequency[index] = self.frequency[index].saturating_sub(n);
+                }
+            }
+
+            /// Erase a record of each field which is set in the target, or return an error
+            /// without erasing any field if one of them has no record.
+            #[inline]
+            #vis fn try_erase #impl_generics (&mut self, target: &#target #ty_generics) -> Result<(), variant_counter::UnderflowError> #where_clause {
+                self.try_erase_n(target, 1)
+            }
+
+            /// Erase `n` records of each field which is set in the target, or return an error
+            /// without erasing any field if one of them has less than `n` records.
+            #vis fn try_erase_n #impl_generics (&mut self, target: &#target #ty_generics, n: usize) -> Result<(), variant_counter::UnderflowError> #where_clause {
+                let flags = Self::flags(target);
+                for index in IntoIterator::into_iter(flags).flatten() {
+                    if self.frequency[index] < n {
+                        return Err(variant_counter::UnderflowError {
+                            name: Self::VARIANT_NAMES[index],
+                            count: self.frequency[index],
+                            requested: n,
+                        });
+                    }
+                }
+                for index in IntoIterator::into_iter(flags).flatten() {
+                    self.frequency[index] -= n;
+                }
+                Ok(())
+            }
+
+            /// Discard the record of each field which is set in the target.
+            /// It has no effect on ignored fields.
+            #vis fn discard #impl_generics (&mut self, target: &#target #ty_generics) #where_clause {
+                for index in IntoIterator::into_iter(Self::flags(target)).flatten() {
+                    self.frequency[index] = 0;
+                }
+            }
+        },
+    };
+    let derives = if parsed.derives.is_empty() {
+        quote! {}
+    } else {
+        let derives = &parsed.derives;
+        quote! { #[derive(#(#derives),*)] }
+    };
 
     let check_fns = &parsed.check_quotes;
+    let rank_fns = derive_rank_fns(vis, "", quote! { self.entries() });
     let erase_fns = &parsed.erase_quotes;
     let group_aggregate_quotes = if parsed.has_customized_group {
         let group_variant_len = parsed.group_aggregate_quotes.len();
         let group_aggregate_quotes = &parsed.group_aggregate_quotes;
+        let group_frequency_quotes = &parsed.group_frequency_quotes;
+        let group_rank_fns = derive_rank_fns(vis, "group_", quote! { self.group_entries() });
         quote! {
             #[cfg(feature = "std")]
             #vis fn group_aggregate(&self) -> std::collections::HashMap<&'static str, usize> {
@@ -62,6 +330,54 @@ fn derive_impl(input: &DeriveInput, parsed: &ParsedEnum) -> proc_macro2::TokenSt
             #vis const fn group_aggregate(&self) -> [(&'static str, usize); #group_variant_len] {
                 [#(#group_aggregate_quotes),*]
             }
+
+            /// The group entries, in the same order as `group_aggregate()`.
+            const fn group_entries(&self) -> [(&'static str, usize); #group_variant_len] {
+                [#(#group_aggregate_quotes),*]
+            }
+
+            #group_rank_fns
+
+            /// The frequency of each group, in the same order as `group_aggregate()`.
+            #[allow(dead_code)]
+            const fn group_frequency(&self) -> [usize; #group_variant_len] {
+                [#(#group_frequency_quotes),*]
+            }
+
+            /// Get the Shannon entropy (in bits) of the group frequency.
+            #[cfg(feature = "stats")]
+            #[inline]
+            #vis fn group_entropy(&self) -> f64 {
+                variant_counter::stats::entropy(&self.group_frequency())
+            }
+
+            /// Get the normalized Shannon entropy of the group frequency.
+            #[cfg(feature = "stats")]
+            #[inline]
+            #vis fn group_normalized_entropy(&self) -> f64 {
+                variant_counter::stats::normalized_entropy(&self.group_frequency())
+            }
+
+            /// Get the Gini impurity of the group frequency.
+            #[cfg(feature = "stats")]
+            #[inline]
+            #vis fn group_gini_impurity(&self) -> f64 {
+                variant_counter::stats::gini_impurity(&self.group_frequency())
+            }
+
+            /// Get the Kullback-Leibler divergence (in bits) of the group frequency from `other`'s.
+            #[cfg(feature = "stats")]
+            #[inline]
+            #vis fn group_kl_divergence(&self, other: &Self) -> f64 {
+                variant_counter::stats::kl_divergence(&self.group_frequency(), &other.group_frequency())
+            }
+
+            /// Get the Jensen-Shannon divergence (in bits) between the group frequency and `other`'s.
+            #[cfg(feature = "stats")]
+            #[inline]
+            #vis fn group_js_divergence(&self, other: &Self) -> f64 {
+                variant_counter::stats::js_divergence(&self.group_frequency(), &other.group_frequency())
+            }
         }
     } else {
         quote! {}
@@ -83,55 +399,220 @@ fn derive_impl(input: &DeriveInput, parsed: &ParsedEnum) -> proc_macro2::TokenSt
             }
         }
 
+        impl #impl_generics variant_counter::Variants for #name #ty_generics #where_clause {
+            type Target = #target #ty_generics;
+            type Array<__T: Copy> = [__T; #variant_len];
+
+            const VARIANT_NAMES: &'static [&'static str] = &#counter_struct::VARIANT_NAMES;
+            const VARIANT_GROUPS: &'static [usize] = &#counter_struct::VARIANT_GROUPS;
+            const GROUP_NAMES: &'static [&'static str] = &#counter_struct::GROUP_NAMES;
+
+            #[inline]
+            fn array<__T: Copy>(value: __T) -> [__T; #variant_len] {
+                [value; #variant_len]
+            }
+
+            #[inline]
+            fn for_each_index(target: &Self::Target, mut f: impl FnMut(usize)) {
+                for index in #counter_struct::indices(target) {
+                    if index < #variant_len {
+                        f(index);
+                    }
+                }
+            }
+        }
+
         /// The concrete counter struct auto-generated by macro.
-        #[derive(Debug, Clone, Copy)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #derives
         #[must_use]
         #vis struct #counter_struct {
             /// An array store the frequency of each variant which not be ignored.
             frequency: [usize; #variant_len],
+            #ignored_field
+        }
+
+        impl Default for #counter_struct {
+            fn default() -> Self {
+                Self::new()
+            }
         }
 
         impl #counter_struct {
+            /// The name of each variant which not be ignored, in the order of the frequency array.
+            #vis const VARIANT_NAMES: [&'static str; #variant_len] = [#(#variant_names),*];
+
+            /// The group index of each variant in `GROUP_NAMES`, in the order of `VARIANT_NAMES`.
+            #vis const VARIANT_GROUPS: [usize; #variant_len] = [#(#variant_groups),*];
+
+            /// The name of each group, an ungrouped variant is a group of its own.
+            #vis const GROUP_NAMES: [&'static str; #group_len] = [#(#group_names),*];
+
+            /// The expected proportion of each variant declared by `#[counter(expected = ...)]`,
+            /// variants without declaration share the remaining proportion evenly.
+            #vis const EXPECTED_PROPORTIONS: [f64; #variant_len] = [#(#expected_proportions),*];
+
+            /// The weight of each variant declared by `#[counter(weight = ...)]`, in the order of `VARIANT_NAMES`.
+            #vis const WEIGHTS: [usize; #variant_len] = [#(#weights),*];
+
+            /// The name of each slot, the variants which not be ignored are followed by
+            /// the ignored variants tracked by `#[counter(ignore, track)]`.
+            const SLOT_NAMES: [&'static str; #slot_len] = [#(#variant_names,)* #(#ignored_names),*];
+
+            /// The hash of the slot names in order, a snapshot can't be decoded by position if it differs.
+            #vis const SCHEMA_HASH: u64 = variant_counter::snapshot::schema_hash(&Self::SLOT_NAMES);
+
+            /// The previous name of each variant declared by `#[counter(alias = "...")]`, along with its slot.
+            const ALIASES: [(&'static str, usize); #alias_len] = [#(#alias_quotes),*];
+
+            /// The name of each removed variant declared by `#[counter(retired = "...")]`.
+            #vis const RETIRED_NAMES: [&'static str; #retired_len] = [#(#retired_names),*];
+
+            /// The layout of the slots, which maps the names in a snapshot to the slots.
+            #vis const SCHEMA: variant_counter::snapshot::Schema<'static> = variant_counter::snapshot::Schema {
+                hash: Self::SCHEMA_HASH,
+                names: &Self::SLOT_NAMES,
+                aliases: &Self::ALIASES,
+                retired: &Self::RETIRED_NAMES,
+            };
+
             #vis const fn new() -> #counter_struct {
                 #counter_struct {
                     frequency: [0; #variant_len],
+                    #ignored_new
                 }
             }
 
-            /// Record a variant. It has no effect if you record an ignored variant.
-            #vis fn record#ty_generics(&mut self, target: &#name#ty_generics) {
-                let pair = match target {
-                    #(#match_arm_quotes,)*
-                    _ => None,
-                };
-
-                if let Some(index) = pair {
-                    self.frequency[index] = self.frequency[index].saturating_add(1);
-                }
-            }
+            #record_fns
 
             #(#erase_fns)*
 
             #(#check_fns)*
 
-            /// Discard the record of the target variant.
-            /// It has no effect if you discard an ignored variant.
-            #vis fn discard#ty_generics(&mut self, target: &#name#ty_generics) {
-                let index = match target {
-                    #(#match_arm_quotes,)*
-                    _ => None,
-                };
+            /// Reset the records.
+            #vis fn reset(&mut self) {
+                self.frequency = [0; #variant_len];
+                #ignored_reset
+            }
 
-                if let Some(index) = index {
-                    self.frequency[index] = 0;
-                }
+            /// Take the records and reset them, like `core::mem::take()`.
+            #[inline]
+            #vis fn take(&mut self) -> #counter_struct {
+                core::mem::replace(self, Self::new())
             }
 
-            /// Reset the records.
-            #vis fn reset#ty_generics(&mut self) {
-                self.frequency = [0; #variant_len];
+            #ignored_fns
+
+            /// Encode the records into a compact binary snapshot, see `variant_counter::snapshot`.
+            #[cfg(feature = "std")]
+            #[inline]
+            #vis fn to_bytes(&self) -> Vec<u8> {
+                self.to_bytes_with(variant_counter::snapshot::Encoding::default())
+            }
+
+            /// Encode the records into a binary snapshot with the `encoding`.
+            #[cfg(feature = "std")]
+            #vis fn to_bytes_with(&self, encoding: variant_counter::snapshot::Encoding) -> Vec<u8> {
+                let mut slots = [0; #slot_len];
+                slots[..#variant_len].copy_from_slice(&self.frequency);
+                #ignored_to_slots
+                variant_counter::snapshot::encode(&Self::SCHEMA, &slots, encoding)
+            }
+
+            /// Decode the records from a binary snapshot.
+            ///
+            /// It fails if the snapshot is encoded by position and the variants have changed.
+            /// The counts of unknown or retired names in a by-name snapshot are dropped silently,
+            /// use `load_bytes()` to inspect them.
+            #[cfg(feature = "std")]
+            #[inline]
+            #vis fn from_bytes(bytes: &[u8]) -> Result<Self, variant_counter::snapshot::SnapshotError> {
+                Self::load_bytes(bytes).map(|loaded| loaded.counter)
+            }
+
+            /// Decode the records from a binary snapshot, along with the counts which can't be mapped to the variants.
+            ///
+            /// The names in a by-name snapshot are mapped by the current names and the aliases declared by `#[counter(alias = "...")]`.
+            #[cfg(feature = "std")]
+            #vis fn load_bytes(bytes: &[u8]) -> Result<variant_counter::snapshot::Loaded<Self>, variant_counter::snapshot::SnapshotError> {
+                let mut slots = [0; #slot_len];
+                let dropped = variant_counter::snapshot::decode(bytes, &Self::SCHEMA, &mut slots)?;
+                let mut counter = Self::new();
+                counter.frequency.copy_from_slice(&slots[..#variant_len]);
+                #ignored_from_slots
+                Ok(variant_counter::snapshot::Loaded { counter, dropped })
+            }
+
+            /// Write the records as `variant,group,count,weighted,percent` rows in the `format`,
+            /// see `variant_counter::table`. Tracked ignored variants are not written, like `aggregate()`.
+            #[cfg(feature = "std")]
+            #vis fn write_csv<W: std::io::Write>(&self, writer: W, format: variant_counter::table::Format) -> std::io::Result<()> {
+                let sum = self.sum();
+                variant_counter::table::write_rows(
+                    writer,
+                    format,
+                    (0..#variant_len).map(|index| variant_counter::table::Row {
+                        variant: Self::VARIANT_NAMES[index],
+                        group: Self::GROUP_NAMES[Self::VARIANT_GROUPS[index]],
+                        count: self.frequency[index],
+                        weighted: self.frequency[index].saturating_mul(Self::WEIGHTS[index]),
+                        percent: if sum == 0 {
+                            0.0
+                        } else {
+                            self.frequency[index] as f64 * 100.0 / sum as f64
+                        },
+                    }),
+                )
             }
 
+            /// Write the records of the counters as a table in the `format`,
+            /// one row per counter under the header of `VARIANT_NAMES`.
+            #[cfg(feature = "std")]
+            #vis fn write_csv_table<'a, W: std::io::Write>(
+                writer: W,
+                format: variant_counter::table::Format,
+                counters: impl IntoIterator<Item = &'a Self>,
+            ) -> std::io::Result<()> {
+                variant_counter::table::write_table(
+                    writer,
+                    format,
+                    &Self::VARIANT_NAMES,
+                    counters.into_iter().map(|counter| &counter.frequency[..]),
+                )
+            }
+
+            /// Read the records from the rows written by `write_csv()`, along with the counts which can't be mapped to the variants.
+            #[cfg(feature = "std")]
+            #vis fn read_csv<R: std::io::Read>(
+                reader: R,
+                format: variant_counter::table::Format,
+            ) -> Result<variant_counter::snapshot::Loaded<Self>, variant_counter::table::TableError> {
+                let mut slots = [0; #slot_len];
+                let dropped = variant_counter::table::read_rows(reader, format, &Self::SCHEMA, &mut slots)?;
+                let mut counter = Self::new();
+                counter.frequency.copy_from_slice(&slots[..#variant_len]);
+                #ignored_from_slots
+                Ok(variant_counter::snapshot::Loaded { counter, dropped })
+            }
+
+            /// Read the records of each row from the table written by `write_csv_table()`.
+            #[cfg(feature = "std")]
+            #vis fn read_csv_table<R: std::io::Read>(
+                reader: R,
+                format: variant_counter::table::Format,
+            ) -> Result<Vec<variant_counter::snapshot::Loaded<Self>>, variant_counter::table::TableError> {
+                let mut loaded = Vec::new();
+                variant_counter::table::read_table(reader, format, &Self::SCHEMA, |slots, dropped| {
+                    let mut counter = Self::new();
+                    counter.frequency.copy_from_slice(&slots[..#variant_len]);
+                    #ignored_from_slots
+                    loaded.push(variant_counter::snapshot::Loaded { counter, dropped });
+                })?;
+                Ok(loaded)
+            }
+
+            #other_fns
+
             /// Aggregate the data to a HashMap.
             #[cfg(feature = "std")]
             #vis fn aggregate(&self) -> std::collections::HashMap<&'static str, usize> {
@@ -146,6 +627,13 @@ fn derive_impl(input: &DeriveInput, parsed: &ParsedEnum) -> proc_macro2::TokenSt
 
             #group_aggregate_quotes
 
+            /// The variant entries, in the same order as `VARIANT_NAMES`.
+            const fn entries(&self) -> [(&'static str, usize); #variant_len] {
+                [#(#aggregate_quotes),*]
+            }
+
+            #rank_fns
+
             /// Get the sum of frequency.
             #[inline]
             #vis fn sum(&self) -> usize {
@@ -177,24 +665,127 @@ fn derive_impl(input: &DeriveInput, parsed: &ParsedEnum) -> proc_macro2::TokenSt
             #vis fn sd(&self) -> f64 {
                 self.variance().sqrt()
             }
+
+            /// Test whether the frequency fits the expected proportions
+            /// declared by `#[counter(expected = ...)]` with Pearson's chi-square test.
+            #[cfg(feature = "stats")]
+            #vis fn chi_square(&self) -> variant_counter::stats::ChiSquare<#variant_len> {
+                self.chi_square_with(&Self::EXPECTED_PROPORTIONS)
+            }
+
+            /// Test whether the frequency is uniformly distributed with Pearson's chi-square test.
+            #[cfg(feature = "stats")]
+            #vis fn chi_square_uniform(&self) -> variant_counter::stats::ChiSquare<#variant_len> {
+                self.chi_square_with(&[1.0; #variant_len])
+            }
+
+            /// Test whether the frequency fits the `expected` proportions with Pearson's chi-square test.
+            /// The proportions are in the same order as `VARIANT_NAMES`.
+            #[cfg(feature = "stats")]
+            #vis fn chi_square_with(
+                &self,
+                expected: &[f64; #variant_len],
+            ) -> variant_counter::stats::ChiSquare<#variant_len> {
+                variant_counter::stats::chi_square(Self::VARIANT_NAMES, &self.frequency, expected)
+            }
+
+            /// Get the Shannon entropy (in bits) of frequency.
+            #[cfg(feature = "stats")]
+            #[inline]
+            #vis fn entropy(&self) -> f64 {
+                variant_counter::stats::entropy(&self.frequency)
+            }
+
+            /// Get the Shannon entropy of frequency normalized to the range `[0.0, 1.0]`.
+            #[cfg(feature = "stats")]
+            #[inline]
+            #vis fn normalized_entropy(&self) -> f64 {
+                variant_counter::stats::normalized_entropy(&self.frequency)
+            }
+
+            /// Get the Gini impurity of frequency.
+            #[cfg(feature = "stats")]
+            #[inline]
+            #vis fn gini_impurity(&self) -> f64 {
+                variant_counter::stats::gini_impurity(&self.frequency)
+            }
+
+            /// Get the Kullback-Leibler divergence (in bits) of frequency from `other`'s.
+            #[cfg(feature = "stats")]
+            #[inline]
+            #vis fn kl_divergence(&self, other: &Self) -> f64 {
+                variant_counter::stats::kl_divergence(&self.frequency, &other.frequency)
+            }
+
+            /// Get the Jensen-Shannon divergence (in bits) between frequency and `other`'s.
+            #[cfg(feature = "stats")]
+            #[inline]
+            #vis fn js_divergence(&self, other: &Self) -> f64 {
+                variant_counter::stats::js_divergence(&self.frequency, &other.frequency)
+            }
         }
+
+        impl variant_counter::NamedCounter for #counter_struct {
+            fn record_name(&mut self, name: &str) -> bool {
+                let index = match name {
+                    #(#name_arm_quotes,)*
+                    _ => None,
+                };
+
+                match #slot_quote {
+                    Some((_, slot)) => {
+                        *slot = slot.saturating_add(1);
+                        true
+                    }
+                    None => false,
+                }
+            }
+        }
+
+        /// Format an aligned table of variant, count, percent and bar.
+        /// The alternate form `{:#}` groups the rows under their group,
+        /// and the width, such as `{:60}`, limits the width of each line.
+        impl core::fmt::Display for #counter_struct {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                variant_counter::display::Table {
+                    entries: &self.entries(),
+                    groups: &Self::VARIANT_GROUPS,
+                    group_names: #display_group_names,
+                }
+                .write(f)
+            }
+        }
+    }
+}
+
+/// The group names to display in the alternate form, which is empty if there is no customized group.
+fn display_group_names(
+    parsed: &ParsedEnum,
+    counter_struct: &proc_macro2::Ident,
+) -> proc_macro2::TokenStream {
+    if parsed.has_customized_group {
+        quote! { &#counter_struct::GROUP_NAMES }
+    } else {
+        quote! { &[] }
     }
 }
 
 fn derive_weighted_impl(input: &DeriveInput, parsed: &ParsedEnum) -> proc_macro2::TokenStream {
-    let name = &input.ident;
     let vis = &input.vis;
 
     let variant_len = parsed.variant_len;
     let weighted_aggregate_quotes = &parsed.weighted_aggregate_quotes;
-    let counter_struct = format_ident!("{}Counter", name);
+    let counter_struct = format_ident!("{}Counter", parsed.type_name);
 
     let weight_check_fns = &parsed.weighted_check_quotes;
     let weights = &parsed.weights;
-    let weighted_struct = format_ident!("{}Weighted", name);
+    let rank_fns = derive_rank_fns(vis, "", quote! { self.entries() });
+    let weighted_struct = format_ident!("{}Weighted", parsed.type_name);
+    let display_group_names = display_group_names(parsed, &counter_struct);
     let weighted_group_aggregate_quotes = if parsed.has_customized_group {
         let group_variant_len = parsed.group_aggregate_quotes.len();
         let weighted_group_aggregate_quotes = &parsed.weighted_group_aggregate_quotes;
+        let group_rank_fns = derive_rank_fns(vis, "group_", quote! { self.group_entries() });
         quote! {
             #[cfg(feature = "std")]
             #vis fn group_aggregate(&self) -> std::collections::HashMap<&'static str, usize> {
@@ -205,6 +796,13 @@ fn derive_weighted_impl(input: &DeriveInput, parsed: &ParsedEnum) -> proc_macro2
             #vis const fn group_aggregate(&self) -> [(&'static str, usize); #group_variant_len] {
                 [#(#weighted_group_aggregate_quotes),*]
             }
+
+            /// The weighted group entries, in the same order as `group_aggregate()`.
+            const fn group_entries(&self) -> [(&'static str, usize); #group_variant_len] {
+                [#(#weighted_group_aggregate_quotes),*]
+            }
+
+            #group_rank_fns
         }
     } else {
         quote! {}
@@ -213,12 +811,13 @@ fn derive_weighted_impl(input: &DeriveInput, parsed: &ParsedEnum) -> proc_macro2
     quote! {
         impl #counter_struct {
             /// Get the weighted counter struct.
-            #vis const fn weighted(&self) -> #weighted_struct {
+            #vis const fn weighted(&self) -> #weighted_struct<'_> {
                 #weighted_struct::new(&self.frequency)
             }
         }
 
         /// The concrete counter struct auto-generated by macro with additional weights.
+        #[derive(Debug, Clone, Copy, PartialEq)]
         #vis struct #weighted_struct<'a> {
             frequency: &'a [usize],
             /// An array store the weight of each variant which not be ignored.
@@ -227,7 +826,7 @@ fn derive_weighted_impl(input: &DeriveInput, parsed: &ParsedEnum) -> proc_macro2
 
         impl<'a> #weighted_struct<'a> {
             /// Create a weighted counter struct
-            #vis const fn new(frequency: &'a [usize]) -> #weighted_struct {
+            #vis const fn new(frequency: &'a [usize]) -> #weighted_struct<'a> {
                 #weighted_struct {
                     frequency,
                     weight: [#(#weights,)*],
@@ -256,6 +855,13 @@ fn derive_weighted_impl(input: &DeriveInput, parsed: &ParsedEnum) -> proc_macro2
 
             #weighted_group_aggregate_quotes
 
+            /// The weighted variant entries, in the same order as the frequency array.
+            const fn entries(&self) -> [(&'static str, usize); #variant_len] {
+                [#(#weighted_aggregate_quotes),*]
+            }
+
+            #rank_fns
+
             /// Get the weighted frequency sum.
             #[inline]
             #vis fn sum(&self) -> usize {
@@ -292,5 +898,626 @@ fn derive_weighted_impl(input: &DeriveInput, parsed: &ParsedEnum) -> proc_macro2
                 self.variance().sqrt()
             }
         }
+
+        /// Format an aligned table of variant, weighted count, percent and bar.
+        /// The alternate form `{:#}` groups the rows under their group,
+        /// and the width, such as `{:60}`, limits the width of each line.
+        impl core::fmt::Display for #weighted_struct<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                variant_counter::display::Table {
+                    entries: &self.entries(),
+                    groups: &#counter_struct::VARIANT_GROUPS,
+                    group_names: #display_group_names,
+                }
+                .write(f)
+            }
+        }
+    }
+}
+
+/// Generate the ranking methods on the `entries` array, the method names are prefixed by `prefix`.
+fn derive_rank_fns(
+    vis: &syn::Visibility,
+    prefix: &str,
+    entries: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let ranked = format_ident!("{}ranked", prefix);
+    let top_k = format_ident!("{}top_k", prefix);
+    let bottom_k = format_ident!("{}bottom_k", prefix);
+    let nonzero = format_ident!("{}nonzero", prefix);
+
+    quote! {
+        /// Iterate the entries ordered by frequency, from the most frequent to the rarest.
+        /// Entries with the same frequency keep their declaration order.
+        #vis fn #ranked(&self) -> impl Iterator<Item = (&'static str, usize)> {
+            variant_counter::rank::descending(#entries)
+        }
+
+        /// Iterate the `k` most frequent entries.
+        #vis fn #top_k(&self, k: usize) -> impl Iterator<Item = (&'static str, usize)> {
+            self.#ranked().take(k)
+        }
+
+        /// Iterate the `k` rarest entries.
+        #vis fn #bottom_k(&self, k: usize) -> impl Iterator<Item = (&'static str, usize)> {
+            variant_counter::rank::ascending(#entries).take(k)
+        }
+
+        /// Iterate the entries which have been recorded, in declaration order.
+        #vis fn #nonzero(&self) -> impl Iterator<Item = (&'static str, usize)> {
+            variant_counter::rank::nonzero(#entries)
+        }
+    }
+}
+
+fn derive_static_impl(input: &DeriveInput, parsed: &ParsedEnum) -> proc_macro2::TokenStream {
+    let vis = &input.vis;
+    let target = &parsed.target;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let variant_len = parsed.variant_len;
+    let ignored_len = parsed.ignored_names.len();
+    let counter_struct = format_ident!("{}Counter", parsed.type_name);
+    let static_struct = format_ident!("{}StaticCounter", parsed.type_name);
+    let (ignored_field, ignored_new, ignored_record, ignored_snapshot, ignored_reset) =
+        if ignored_len > 0 {
+            (
+                quote! {
+                    /// The count of each ignored variant tracked by `#[counter(ignore, track)]`.
+                    ignored: [variant_counter::sync::Count; #ignored_len],
+                },
+                quote! { ignored: [const { variant_counter::sync::Count::new() }; #ignored_len], },
+                quote! {
+                    if index >= #variant_len {
+                        self.ignored[index - #variant_len].add(1);
+                        continue;
+                    }
+                },
+                quote! {
+                    for (count, slot) in self.ignored.iter().zip(counter.ignored.iter_mut()) {
+                        *slot = read(count);
+                    }
+                },
+                quote! {
+                    for count in self.ignored.iter() {
+                        count.reset();
+                    }
+                },
+            )
+        } else {
+            (quote! {}, quote! {}, quote! {}, quote! {}, quote! {})
+        };
+
+    quote! {
+        /// The counter which can be recorded by a shared reference, such as in a `static` item.
+        ///
+        /// Each count is an atomic, or is protected by a critical section on targets without atomics.
+        #[derive(Debug)]
+        #vis struct #static_struct {
+            /// The count of each variant which not be ignored.
+            frequency: [variant_counter::sync::Count; #variant_len],
+            #ignored_field
+        }
+
+        impl Default for #static_struct {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl #static_struct {
+            #vis const fn new() -> #static_struct {
+                #static_struct {
+                    frequency: [const { variant_counter::sync::Count::new() }; #variant_len],
+                    #ignored_new
+                }
+            }
+
+            /// Record a variant. It has no effect if you record an ignored variant.
+            #vis fn record #impl_generics (&self, target: &#target #ty_generics) #where_clause {
+                for index in #counter_struct::indices(target) {
+                    #ignored_record
+                    self.frequency[index].add(1);
+                }
+            }
+
+            /// Get a snapshot of the records as a normal counter.
+            ///
+            /// Each count is read on its own, records made during the snapshot may be partially included.
+            #[inline]
+            #vis fn snapshot(&self) -> #counter_struct {
+                self.collect(variant_counter::sync::Count::load)
+            }
+
+            /// Take the records as a normal counter and reset them.
+            ///
+            /// Each count is swapped with zero atomically, so every record is included
+            /// in exactly one of the taken counters even if it is recorded concurrently.
+            #[inline]
+            #vis fn take(&self) -> #counter_struct {
+                self.collect(|count| count.swap(0))
+            }
+
+            fn collect(&self, read: impl Fn(&variant_counter::sync::Count) -> usize) -> #counter_struct {
+                let mut counter = #counter_struct::new();
+                for (count, slot) in self.frequency.iter().zip(counter.frequency.iter_mut()) {
+                    *slot = read(count);
+                }
+                #ignored_snapshot
+                counter
+            }
+
+            /// Reset the records.
+            #vis fn reset(&self) {
+                for count in self.frequency.iter() {
+                    count.reset();
+                }
+                #ignored_reset
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl variant_counter::report::Take for #static_struct {
+            type Counter = #counter_struct;
+
+            #[inline]
+            fn take(&self) -> #counter_struct {
+                #static_struct::take(self)
+            }
+        }
+    }
+}
+
+fn derive_sharded_impl(input: &DeriveInput, parsed: &ParsedEnum) -> proc_macro2::TokenStream {
+    let vis = &input.vis;
+    let target = &parsed.target;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let variant_len = parsed.variant_len;
+    let ignored_len = parsed.ignored_names.len();
+    // Each shard has a slot for each variant and each tracked ignored variant.
+    let shard_len = variant_len + ignored_len;
+    let counter_struct = format_ident!("{}Counter", parsed.type_name);
+    let sharded_struct = format_ident!("{}ShardedCounter", parsed.type_name);
+    let ignored_snapshot = if ignored_len > 0 {
+        quote! {
+            if index >= #variant_len {
+                let slot = &mut counter.ignored[index - #variant_len];
+                *slot = slot.saturating_add(read(count));
+                continue;
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        /// The counter which records into the shard of the current thread, to avoid contention
+        /// between threads. Each shard is padded to its own cache line.
+        ///
+        /// The shards are combined on `snapshot()`, which is eventually consistent with the records.
+        #[cfg(feature = "std")]
+        #[derive(Debug)]
+        #vis struct #sharded_struct<const __SHARDS: usize = 16> {
+            shards: [variant_counter::sync::CachePadded<[variant_counter::sync::Count; #shard_len]>; __SHARDS],
+        }
+
+        #[cfg(feature = "std")]
+        impl #sharded_struct {
+            /// Create a counter with 16 shards.
+            #vis const fn new() -> #sharded_struct {
+                Self::with_shards()
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl<const __SHARDS: usize> Default for #sharded_struct<__SHARDS> {
+            fn default() -> Self {
+                Self::with_shards()
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl<const __SHARDS: usize> #sharded_struct<__SHARDS> {
+            /// Create a counter with the number of shards given by the const parameter.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the number of shards is zero.
+            #vis const fn with_shards() -> Self {
+                assert!(__SHARDS > 0, "a sharded counter must have at least one shard");
+                #sharded_struct {
+                    shards: [const {
+                        variant_counter::sync::CachePadded::new(
+                            [const { variant_counter::sync::Count::new() }; #shard_len],
+                        )
+                    }; __SHARDS],
+                }
+            }
+
+            /// Record a variant. It has no effect if you record an ignored variant.
+            #vis fn record #impl_generics (&self, target: &#target #ty_generics) #where_clause {
+                let shard = &self.shards[variant_counter::sync::shard_index(__SHARDS)];
+                for index in #counter_struct::indices(target) {
+                    shard[index].add(1);
+                }
+            }
+
+            /// Combine the shards into a normal counter.
+            #[inline]
+            #vis fn snapshot(&self) -> #counter_struct {
+                self.collect(variant_counter::sync::Count::load)
+            }
+
+            /// Combine the shards into a normal counter and reset them.
+            ///
+            /// Each count is swapped with zero atomically, so every record is included
+            /// in exactly one of the taken counters even if it is recorded concurrently.
+            #[inline]
+            #vis fn take(&self) -> #counter_struct {
+                self.collect(|count| count.swap(0))
+            }
+
+            fn collect(&self, read: impl Fn(&variant_counter::sync::Count) -> usize) -> #counter_struct {
+                let mut counter = #counter_struct::new();
+                for shard in self.shards.iter() {
+                    for (index, count) in shard.iter().enumerate() {
+                        #ignored_snapshot
+                        counter.frequency[index] = counter.frequency[index].saturating_add(read(count));
+                    }
+                }
+                counter
+            }
+
+            /// Reset the records.
+            #vis fn reset(&self) {
+                for shard in self.shards.iter() {
+                    for count in shard.iter() {
+                        count.reset();
+                    }
+                }
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl<const __SHARDS: usize> variant_counter::report::Take for #sharded_struct<__SHARDS> {
+            type Counter = #counter_struct;
+
+            #[inline]
+            fn take(&self) -> #counter_struct {
+                #sharded_struct::take(self)
+            }
+        }
+    }
+}
+
+fn derive_sampled_impl(input: &DeriveInput, parsed: &ParsedEnum) -> proc_macro2::TokenStream {
+    let vis = &input.vis;
+    let target = &parsed.target;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let variant_len = parsed.variant_len;
+    let counter_struct = format_ident!("{}Counter", parsed.type_name);
+    let sampled_struct = format_ident!("{}SampledCounter", parsed.type_name);
+
+    quote! {
+        /// The counter which records only a sample of the values and scales the counts back up,
+        /// for the hot paths where counting every value costs too much.
+        #[cfg(feature = "std")]
+        #[derive(Debug, Clone)]
+        #vis struct #sampled_struct<__R = variant_counter::sample::SplitMix64> {
+            /// The records of the sampled values.
+            counter: #counter_struct,
+            sampler: variant_counter::sample::Sampler<__R>,
+        }
+
+        #[cfg(feature = "std")]
+        impl #sampled_struct {
+            /// Create a counter with the `SplitMix64` generator seeded by `seed`.
+            #vis fn new(sampling: variant_counter::sample::Sampling, seed: u64) -> #sampled_struct {
+                Self::with_rng(sampling, variant_counter::sample::SplitMix64::new(seed))
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl<__R: variant_counter::sample::Rng> #sampled_struct<__R> {
+            /// Create a counter with the random number generator.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the sampling is invalid, see `variant_counter::sample::Sampler::new()`.
+            #vis fn with_rng(sampling: variant_counter::sample::Sampling, rng: __R) -> Self {
+                #sampled_struct {
+                    counter: #counter_struct::new(),
+                    sampler: variant_counter::sample::Sampler::new(sampling, rng),
+                }
+            }
+
+            /// Record a variant if it is sampled. It has no effect if you record an ignored variant.
+            #[inline]
+            #vis fn record #impl_generics (&mut self, target: &#target #ty_generics) #where_clause {
+                if self.sampler.sample() {
+                    self.counter.record(target);
+                }
+            }
+
+            /// Get the records of the sampled values, which are not scaled.
+            #[inline]
+            #vis const fn sampled(&self) -> &#counter_struct {
+                &self.counter
+            }
+
+            /// Get the sampling.
+            #[inline]
+            #vis fn sampling(&self) -> variant_counter::sample::Sampling {
+                self.sampler.sampling()
+            }
+
+            /// Get the estimated sum of all values.
+            #[inline]
+            #vis fn sum(&self) -> f64 {
+                self.counter.sum() as f64 / self.sampling().rate()
+            }
+
+            /// Aggregate the estimated count of each variant to a HashMap.
+            #vis fn aggregate(&self) -> std::collections::HashMap<&'static str, f64> {
+                let rate = self.sampling().rate();
+                self.counter
+                    .entries()
+                    .iter()
+                    .map(|(name, freq)| (*name, *freq as f64 / rate))
+                    .collect()
+            }
+
+            /// Estimate the count of each variant along with its confidence interval
+            /// at the `confidence` level, such as `0.95`, in the order of `VARIANT_NAMES`.
+            #vis fn estimates(&self, confidence: f64) -> [variant_counter::sample::Estimate; #variant_len] {
+                let rate = self.sampling().rate();
+                let entries = self.counter.entries();
+                let mut estimates = [variant_counter::sample::Estimate {
+                    name: "",
+                    sampled: 0,
+                    estimate: 0.0,
+                    low: 0.0,
+                    high: 0.0,
+                }; #variant_len];
+                for (estimate, (name, freq)) in estimates.iter_mut().zip(entries) {
+                    *estimate = variant_counter::sample::estimate(name, freq, rate, confidence);
+                }
+                estimates
+            }
+
+            /// Reset the records, the sampling goes on.
+            #vis fn reset(&mut self) {
+                self.counter.reset();
+            }
+        }
+    }
+}
+
+fn derive_proportions_impl(input: &DeriveInput, parsed: &ParsedEnum) -> proc_macro2::TokenStream {
+    let vis = &input.vis;
+
+    let variant_len = parsed.variant_len;
+    let counter_struct = format_ident!("{}Counter", parsed.type_name);
+    let proportions_struct = format_ident!("{}Proportions", parsed.type_name);
+    let proportion_check_fns = &parsed.proportion_check_quotes;
+
+    let (group_entries_field, group_entries_value, group_fns) = if parsed.has_customized_group {
+        let group_variant_len = parsed.group_aggregate_quotes.len();
+        (
+            quote! { group_entries: [(&'static str, usize); #group_variant_len], },
+            quote! { group_entries: self.group_entries(), },
+            quote! {
+                /// Aggregate the share of each group to a HashMap.
+                #[cfg(feature = "std")]
+                #vis fn group_aggregate(&self) -> std::collections::HashMap<&'static str, f64> {
+                    self.group_entries
+                        .iter()
+                        .map(|(name, freq)| (*name, self.share(*freq)))
+                        .collect()
+                }
+
+                /// Aggregate the share of each group to an array.
+                #[cfg(not(feature = "std"))]
+                #vis fn group_aggregate(&self) -> [(&'static str, f64); #group_variant_len] {
+                    let mut shares = [("", 0.0); #group_variant_len];
+                    for (share, (name, freq)) in shares.iter_mut().zip(self.group_entries) {
+                        *share = (name, self.share(freq));
+                    }
+                    shares
+                }
+            },
+        )
+    } else {
+        (quote! {}, quote! {}, quote! {})
+    };
+    let group_display = if parsed.has_customized_group {
+        quote! {
+            if f.alternate() {
+                return variant_counter::display::write_shares(f, &self.group_entries, self.total);
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        impl #counter_struct {
+            /// Get the proportions view, which is the share of each variant in the total frequency.
+            #vis fn proportions(&self) -> #proportions_struct {
+                #proportions_struct {
+                    entries: self.entries(),
+                    #group_entries_value
+                    total: self.sum(),
+                }
+            }
+        }
+
+        /// The share of each variant in the total frequency, auto-generated by macro.
+        ///
+        /// All the shares are `0.0` if nothing has been recorded.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        #vis struct #proportions_struct {
+            entries: [(&'static str, usize); #variant_len],
+            #group_entries_field
+            total: usize,
+        }
+
+        impl #proportions_struct {
+            /// Get the total frequency.
+            #[inline]
+            #vis const fn total(&self) -> usize {
+                self.total
+            }
+
+            /// Whether nothing has been recorded.
+            #[inline]
+            #vis const fn is_empty(&self) -> bool {
+                self.total == 0
+            }
+
+            #[inline]
+            fn share(&self, freq: usize) -> f64 {
+                variant_counter::display::share(freq, self.total)
+            }
+
+            #(#proportion_check_fns)*
+
+            /// Aggregate the share of each variant to a HashMap.
+            #[cfg(feature = "std")]
+            #vis fn aggregate(&self) -> std::collections::HashMap<&'static str, f64> {
+                self.entries
+                    .iter()
+                    .map(|(name, freq)| (*name, self.share(*freq)))
+                    .collect()
+            }
+
+            /// Aggregate the share of each variant to an array.
+            #[cfg(not(feature = "std"))]
+            #vis fn aggregate(&self) -> [(&'static str, f64); #variant_len] {
+                let mut shares = [("", 0.0); #variant_len];
+                for (share, (name, freq)) in shares.iter_mut().zip(self.entries) {
+                    *share = (name, self.share(freq));
+                }
+                shares
+            }
+
+            #group_fns
+        }
+
+        /// Format the share of each variant as percentages, such as `A: 66.67%, B: 33.33%`.
+        /// The alternate form `{:#}` formats the share of each group instead.
+        impl core::fmt::Display for #proportions_struct {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                #group_display
+                variant_counter::display::write_shares(f, &self.entries, self.total)
+            }
+        }
+    }
+}
+
+fn derive_diff_impl(input: &DeriveInput, parsed: &ParsedEnum) -> proc_macro2::TokenStream {
+    let vis = &input.vis;
+
+    let variant_len = parsed.variant_len;
+    let counter_struct = format_ident!("{}Counter", parsed.type_name);
+    let diff_struct = format_ident!("{}Diff", parsed.type_name);
+
+    let (group_field, group_value, group_fns, group_display) = if parsed.has_customized_group {
+        let group_variant_len = parsed.group_aggregate_quotes.len();
+        (
+            quote! { groups: [variant_counter::diff::Change; #group_variant_len], },
+            quote! { groups: variant_counter::diff::changes(self.group_entries(), other.group_entries()), },
+            quote! {
+                /// The change of each group, in the same order as `group_aggregate()`.
+                #[inline]
+                #vis fn groups(&self) -> &[variant_counter::diff::Change] {
+                    &self.groups
+                }
+            },
+            quote! {
+                if f.alternate() {
+                    return variant_counter::diff::write_changes(f, &self.groups);
+                }
+            },
+        )
+    } else {
+        (quote! {}, quote! {}, quote! {}, quote! {})
+    };
+
+    quote! {
+        impl #counter_struct {
+            /// Compare with the `other` counter which is recorded later, such as after a deploy.
+            #vis fn diff(&self, other: &Self) -> #diff_struct {
+                #diff_struct {
+                    variants: variant_counter::diff::changes(self.entries(), other.entries()),
+                    #group_value
+                    total: variant_counter::diff::Change {
+                        name: "total",
+                        before: self.sum(),
+                        after: other.sum(),
+                    },
+                }
+            }
+        }
+
+        /// The changes of each variant between two counters, auto-generated by macro.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #vis struct #diff_struct {
+            variants: [variant_counter::diff::Change; #variant_len],
+            #group_field
+            total: variant_counter::diff::Change,
+        }
+
+        impl #diff_struct {
+            /// The change of each variant, in the same order as `VARIANT_NAMES`.
+            #[inline]
+            #vis fn variants(&self) -> &[variant_counter::diff::Change] {
+                &self.variants
+            }
+
+            /// The change of the variant by its display name.
+            #vis fn get(&self, name: &str) -> Option<variant_counter::diff::Change> {
+                self.variants.iter().find(|change| change.name == name).copied()
+            }
+
+            /// The change of the total frequency.
+            #[inline]
+            #vis const fn total(&self) -> variant_counter::diff::Change {
+                self.total
+            }
+
+            /// Whether no variant has changed.
+            #vis fn is_empty(&self) -> bool {
+                !self.variants.iter().any(|change| change.is_changed())
+            }
+
+            /// The variants whose count has changed.
+            #vis fn changed(&self) -> impl Iterator<Item = &variant_counter::diff::Change> + '_ {
+                self.variants.iter().filter(|change| change.is_changed())
+            }
+
+            /// The variants which went from zero to non-zero.
+            #vis fn appeared(&self) -> impl Iterator<Item = &variant_counter::diff::Change> + '_ {
+                self.variants.iter().filter(|change| change.appeared())
+            }
+
+            /// The variants which went from non-zero to zero.
+            #vis fn vanished(&self) -> impl Iterator<Item = &variant_counter::diff::Change> + '_ {
+                self.variants.iter().filter(|change| change.vanished())
+            }
+
+            #group_fns
+        }
+
+        /// Format the changed variants, such as `Ok: 4 -> 6 (+2, +50.00%), Timeout: 0 -> 3 (+3, new)`,
+        /// or `no change`. The alternate form `{:#}` formats the changed groups instead.
+        impl core::fmt::Display for #diff_struct {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                #group_display
+                variant_counter::diff::write_changes(f, &self.variants)
+            }
+        }
     }
 }
@@ -1,25 +1,54 @@
 use std::collections::HashMap;
 
-use quote::{format_ident, quote};
-use syn::{DataEnum, DeriveInput, Fields};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{spanned::Spanned, DataEnum, DeriveInput, Fields, FieldsNamed, Variant};
 
-use crate::attrs::ParsedAttr;
+use crate::{attrs::ParsedAttr, case};
 
 pub(crate) struct ParsedEnum {
+    // The path of the recorded type, which is the remote type declared by `#[counter(remote = "...")]` or the type itself.
+    pub(crate) target: proc_macro2::TokenStream,
+    // The prefix of the generated types, such as `{Enum}Counter`, which is the last segment of the remote type if any.
+    pub(crate) type_name: proc_macro2::Ident,
     // The number of variants in the enum type.
     pub(crate) variant_count: usize,
     // The number of variants excluding ignored in the enum type.
     pub(crate) variant_len: usize,
+    // The match arms map a variant to its slot, the slots of tracked ignored variants follow the frequency array.
     pub(crate) match_arm_quotes: Vec<proc_macro2::TokenStream>,
+    // The index of each field if it is set, in the order of the frequency array. It is `Some` only for structs.
+    pub(crate) flag_quotes: Option<Vec<proc_macro2::TokenStream>>,
+    // The match arms map the display name or identifier of a variant to its slot.
+    pub(crate) name_arm_quotes: Vec<proc_macro2::TokenStream>,
+    // The index of the bucket which records unknown variants of a remote or `#[non_exhaustive]` enum.
+    pub(crate) other_index: Option<usize>,
+    // The display name of each variant excluding ignored.
+    pub(crate) variant_names: Vec<String>,
+    // The display name of each ignored variant which is tracked by `#[counter(ignore, track)]`.
+    pub(crate) ignored_names: Vec<String>,
+    // The previous name of a variant declared by `#[counter(alias = "...")]` along with its slot.
+    pub(crate) alias_quotes: Vec<proc_macro2::TokenStream>,
+    // The name of each removed variant declared by `#[counter(retired = "...")]`.
+    pub(crate) retired_names: Vec<String>,
+    // The group index of each variant excluding ignored.
+    pub(crate) variant_groups: Vec<usize>,
+    // The name of each group, including the implicit group of ungrouped variants.
+    pub(crate) group_names: Vec<String>,
+    // The expected proportion of each variant excluding ignored.
+    pub(crate) expected_proportions: Vec<f64>,
     pub(crate) weights: Vec<proc_macro2::TokenStream>,
     pub(crate) check_quotes: Vec<proc_macro2::TokenStream>,
     pub(crate) weighted_check_quotes: Vec<proc_macro2::TokenStream>,
+    pub(crate) proportion_check_quotes: Vec<proc_macro2::TokenStream>,
     pub(crate) erase_quotes: Vec<proc_macro2::TokenStream>,
     pub(crate) aggregate_quotes: Vec<proc_macro2::TokenStream>,
     pub(crate) group_aggregate_quotes: Vec<proc_macro2::TokenStream>,
+    pub(crate) group_frequency_quotes: Vec<proc_macro2::TokenStream>,
     pub(crate) weighted_aggregate_quotes: Vec<proc_macro2::TokenStream>,
     pub(crate) weighted_group_aggregate_quotes: Vec<proc_macro2::TokenStream>,
     pub(crate) has_customized_group: bool,
+    // The extra derives of the counter struct.
+    pub(crate) derives: Vec<proc_macro2::TokenStream>,
 }
 
 impl ParsedEnum {
@@ -27,95 +56,326 @@ impl ParsedEnum {
         input: &DeriveInput,
         data_enum: &DataEnum,
         parsed_attr: &ParsedAttr,
-    ) -> Self {
-        let name = &input.ident;
+    ) -> Result<Self, proc_macro2::TokenStream> {
+        let (target, type_name) = match &parsed_attr.remote {
+            Some((path, ident)) => (path.clone(), ident.clone()),
+            None => {
+                let name = &input.ident;
+                (quote! { #name }, name.clone())
+            }
+        };
         let vis = &input.vis;
         let variant_count = data_enum.variants.len();
-        let variant_len = variant_count - parsed_attr.ignores.len();
+        let variant_len =
+            variant_count - parsed_attr.ignores.len() + parsed_attr.other.iter().count();
         let mut weights = Vec::with_capacity(variant_len);
         let mut check_quotes = Vec::with_capacity(variant_len);
         let mut weighted_check_quotes = Vec::with_capacity(variant_len);
+        let mut proportion_check_quotes = Vec::with_capacity(variant_len);
         let mut erase_quotes = Vec::with_capacity(variant_len);
         let mut match_arm_quotes = Vec::with_capacity(variant_len);
+        let mut name_arm_quotes = Vec::with_capacity(variant_len);
+        let mut variant_names = Vec::with_capacity(variant_len);
+        let mut variant_groups = Vec::with_capacity(variant_len);
+        let mut expected_proportions = Vec::with_capacity(variant_len);
+        // Variants without a declared `expected` share the remaining proportion evenly.
+        let undeclared_expected = variant_len - parsed_attr.expected.len();
+        let remaining_expected = if parsed_attr.expected.is_empty() {
+            1.0
+        } else {
+            (1.0 - parsed_attr.expected.values().sum::<f64>()).max(0.0)
+        };
         let mut aggregate_quotes = Vec::with_capacity(variant_len);
         let mut weighted_aggregate_quotes = Vec::with_capacity(variant_len);
         let variant_index_map = data_enum
             .variants
             .iter()
             .filter(|variant| !parsed_attr.is_ignored(variant))
+            .map(|variant| &variant.ident)
+            .chain(parsed_attr.other.as_ref())
             .enumerate()
-            .map(|(index, variant)| (&variant.ident, index))
+            .map(|(index, ident)| (ident, index))
             .collect::<HashMap<&proc_macro2::Ident, usize>>();
 
-        data_enum
+        // The variant which has generated the method suffix or display name, to detect collisions.
+        let mut method_suffixes = HashMap::<String, &proc_macro2::Ident>::new();
+        let mut display_names = HashMap::<String, &proc_macro2::Ident>::new();
+
+        for variant in data_enum
             .variants
             .iter()
             .filter(|variant| !parsed_attr.is_ignored(variant))
-            .for_each(|variant| {
-                let variant_name = &variant.ident;
-                let index = variant_index_map[variant_name];
-                let display_variant_name = variant_name.to_string();
-
-                let check_fn_name = format_ident!("check_{}", display_variant_name.to_lowercase());
-                check_quotes.push(quote! {
-                    /// Check the variant's frequency.
-                    #[cfg(feature = "check")]
-                    #[inline]
-                    #vis const fn #check_fn_name(&self) -> usize {
-                        self.frequency[#index]
-                    }
+        {
+            let variant_name = &variant.ident;
+            let index = variant_index_map[variant_name];
+            let display_variant_name = parsed_attr.display_name(variant_name);
+            let method_name = parsed_attr.method_name(variant_name);
+            let method_suffix = case::to_snake_case(&method_name);
+            if syn::parse_str::<syn::Ident>(&format!("check_{}", method_suffix)).is_err() {
+                let message = format!(
+                    "Invalid method name `check_{}` generated from `{}`, please rename it with #[counter(rename = \"...\")]",
+                    method_suffix, method_name
+                );
+                return Err(quote_spanned! {variant_name.span()=>
+                    compile_error!(#message);
                 });
-                weighted_check_quotes.push(quote! {
-                    /// Check the variant's weighted frequency.
-                    #[cfg(feature = "check")]
-                    #[inline]
-                    #vis const fn #check_fn_name(&self) -> usize {
-                        self.frequency[#index] * self.weight[#index]
-                    }
+            }
+            if method_suffix == "n" {
+                let message = format!(
+                    "Variant `{}` generates the method name `erase_n` which is reserved, please rename it with #[counter(rename = \"...\")]",
+                    variant_name
+                );
+                return Err(quote_spanned! {variant_name.span()=>
+                    compile_error!(#message);
                 });
-                aggregate_quotes.push(quote! {
-                    (#display_variant_name, self.frequency[#index])
+            }
+            if let Some(other) = method_suffixes.insert(method_suffix.clone(), variant_name) {
+                let message = format!(
+                    "Variant `{}` generates the same method name `check_{}` as `{}`, please rename it with #[counter(rename = \"...\")]",
+                    variant_name, method_suffix, other
+                );
+                return Err(quote_spanned! {variant_name.span()=>
+                    compile_error!(#message);
                 });
-                weighted_aggregate_quotes.push(quote! {
-                    (#display_variant_name, self.frequency[#index] * self.weight[#index])
+            }
+            if let Some(other) = display_names.insert(display_variant_name.clone(), variant_name) {
+                let message = format!(
+                    "Variant `{}` has the same name `{}` as `{}`, please rename it with #[counter(rename = \"...\")]",
+                    variant_name, display_variant_name, other
+                );
+                return Err(quote_spanned! {variant_name.span()=>
+                    compile_error!(#message);
                 });
+            }
+            variant_names.push(display_variant_name.clone());
+            name_arm_quotes.push(name_arm(&display_variant_name, variant_name, index));
+            variant_groups.push(
+                parsed_attr
+                    .index_group(variant_name)
+                    .map(|(index, _)| index)
+                    .expect("non-ignored variant must have a group"),
+            );
+            expected_proportions.push(
+                parsed_attr
+                    .expected
+                    .get(variant_name)
+                    .copied()
+                    .unwrap_or_else(|| remaining_expected / undeclared_expected as f64),
+            );
 
-                match &variant.fields {
-                    Fields::Named(_) => {
-                        match_arm_quotes.push(quote! {
-                            #name::#variant_name{ .. } => Some(#index)
-                        });
-                    }
-                    Fields::Unnamed(f) => {
-                        if f.unnamed.is_empty() {
-                            match_arm_quotes.push(quote! {
-                                #name::#variant_name() => Some(#index)
-                            });
-                        } else {
-                            match_arm_quotes.push(quote! {
-                                #name::#variant_name(..) => Some(#index)
-                            });
-                        }
-                    }
-                    Fields::Unit => match_arm_quotes.push(quote! {
-                        #name::#variant_name => Some(#index)
-                    }),
+            let check_fn_name = format_ident!("check_{}", method_suffix);
+            check_quotes.push(quote! {
+                /// Check the variant's frequency.
+                #[cfg(feature = "check")]
+                #[inline]
+                #vis const fn #check_fn_name(&self) -> usize {
+                    self.frequency[#index]
+                }
+            });
+            weighted_check_quotes.push(quote! {
+                /// Check the variant's weighted frequency.
+                #[cfg(feature = "check")]
+                #[inline]
+                #vis const fn #check_fn_name(&self) -> usize {
+                    self.frequency[#index] * self.weight[#index]
                 }
+            });
+            proportion_check_quotes.push(quote! {
+                /// Check the variant's share of the total.
+                #[cfg(feature = "check")]
+                #[inline]
+                #vis fn #check_fn_name(&self) -> f64 {
+                    self.share(self.entries[#index].1)
+                }
+            });
+            aggregate_quotes.push(quote! {
+                (#display_variant_name, self.frequency[#index])
+            });
+            weighted_aggregate_quotes.push(quote! {
+                (#display_variant_name, self.frequency[#index] * self.weight[#index])
+            });
 
-                weights.push(parsed_attr.weight.get(variant_name).copied().unwrap_or(1));
+            let pattern = variant_pattern(&target, variant);
+            if let Some(predicate) = parsed_attr.predicates.get(variant_name) {
+                // Record the variant only if its fields satisfy the predicate,
+                // otherwise record the fallback variant, or nothing.
+                let (binding_pattern, bindings) = variant_bindings(&target, variant);
+                let fallback = match parsed_attr.fallbacks.get(variant_name) {
+                    Some(fallback) => {
+                        let fallback_index = variant_index_map[fallback];
+                        quote! { Some(#fallback_index) }
+                    }
+                    None => quote! { None },
+                };
+                match_arm_quotes.push(quote! {
+                    #binding_pattern if #predicate(#(#bindings),*) => Some(#index)
+                });
+                match_arm_quotes.push(quote! {
+                    #pattern => #fallback
+                });
+            } else {
+                match_arm_quotes.push(quote! {
+                    #pattern => Some(#index)
+                });
+            }
 
-                let erase_fn_name = format_ident!("erase_{}", display_variant_name.to_lowercase());
-                erase_quotes.push(quote! {
-                    /// Erase a record.
-                    /// It has no effect if you erase an ignored variant.
-                    #[cfg(feature = "erase")]
-                    #[inline]
-                    #vis fn #erase_fn_name(&mut self) {
-                        self.frequency[#index] = self.frequency[#index].saturating_sub(1);
+            weights.push(parsed_attr.weight.get(variant_name).copied().unwrap_or(1));
+
+            let erase_fn_name = format_ident!("erase_{}", method_suffix);
+            erase_quotes.push(quote! {
+                /// Erase a record.
+                /// It has no effect if you erase an ignored variant.
+                #[cfg(feature = "erase")]
+                #[inline]
+                #vis fn #erase_fn_name(&mut self) {
+                    self.frequency[#index] = self.frequency[#index].saturating_sub(1);
+                }
+            });
+        }
+        let other_index = parsed_attr
+            .other
+            .as_ref()
+            .map(|other| variant_index_map[other]);
+        if let Some(other) = &parsed_attr.other {
+            let display_other_name = parsed_attr.display_name(other);
+            if let Some(variant) = display_names.get(&display_other_name) {
+                let message = format!(
+                    "Variant `{}` has the same name `{}` as the other bucket, please rename it with #[counter(rename = \"...\")]",
+                    variant, display_other_name
+                );
+                return Err(quote_spanned! {variant.span()=>
+                    compile_error!(#message);
+                });
+            }
+            variant_names.push(display_other_name.clone());
+            variant_groups.push(
+                parsed_attr
+                    .index_group(other)
+                    .map(|(index, _)| index)
+                    .expect("the other bucket must have a group"),
+            );
+            expected_proportions.push(remaining_expected / undeclared_expected as f64);
+            aggregate_quotes.push(quote! {
+                (#display_other_name, self.frequency[#other_index])
+            });
+            weighted_aggregate_quotes.push(quote! {
+                (#display_other_name, self.frequency[#other_index] * self.weight[#other_index])
+            });
+            weights.push(1);
+        }
+
+        let mut ignored_names = Vec::with_capacity(parsed_attr.tracks.len());
+        let mut offset = 0;
+        for variant in data_enum
+            .variants
+            .iter()
+            .filter(|variant| parsed_attr.is_ignored(variant))
+        {
+            let pattern = variant_pattern(&target, variant);
+            if parsed_attr.is_tracked(variant) {
+                // The slots of tracked ignored variants follow the frequency array.
+                let index = variant_len + offset;
+                offset += 1;
+                match_arm_quotes.push(quote! {
+                    #pattern => Some(#index)
+                });
+                ignored_names.push(parsed_attr.display_name(&variant.ident));
+                name_arm_quotes.push(name_arm(
+                    &parsed_attr.display_name(&variant.ident),
+                    &variant.ident,
+                    index,
+                ));
+            } else {
+                match_arm_quotes.push(quote! {
+                    #pattern => None
+                });
+            }
+        }
+        // The names recorded by `record_name` must map to a single variant.
+        let mut record_names = HashMap::<String, &proc_macro2::Ident>::new();
+        for variant_name in data_enum
+            .variants
+            .iter()
+            .filter(|variant| !parsed_attr.is_ignored(variant) || parsed_attr.is_tracked(variant))
+            .map(|variant| &variant.ident)
+        {
+            for name in [
+                parsed_attr.display_name(variant_name),
+                variant_name.to_string(),
+            ] {
+                match record_names.insert(name.clone(), variant_name) {
+                    Some(other) if other != variant_name => {
+                        let message = format!(
+                            "The name `{}` of variant `{}` is already the name of `{}`, please rename it with #[counter(rename = \"...\")]",
+                            name, variant_name, other
+                        );
+                        return Err(quote_spanned! {variant_name.span()=>
+                            compile_error!(#message);
+                        });
                     }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(other) = &parsed_attr.other {
+            let display_other_name = parsed_attr.display_name(other);
+            match record_names.insert(display_other_name.clone(), other) {
+                Some(variant) if variant != other => {
+                    let message = format!(
+                        "Variant `{}` has the same name `{}` as the other bucket, please rename it with #[counter(rename = \"...\")]",
+                        variant, display_other_name
+                    );
+                    return Err(quote_spanned! {variant.span()=>
+                        compile_error!(#message);
+                    });
+                }
+                _ => {}
+            }
+        }
+        // The previous names must not be taken by any current variant, nor by each other.
+        let mut taken_names = record_names;
+        let mut alias_quotes = Vec::with_capacity(parsed_attr.aliases.len());
+        for variant in data_enum.variants.iter() {
+            let variant_name = &variant.ident;
+            for alias in parsed_attr.aliases.get(variant_name).into_iter().flatten() {
+                if taken_names.insert(alias.clone(), variant_name).is_some() {
+                    let message = format!(
+                        "The alias `{}` of variant `{}` is already the name of a variant or another alias",
+                        alias, variant_name
+                    );
+                    return Err(quote_spanned! {variant_name.span()=>
+                        compile_error!(#message);
+                    });
+                }
+                let index = variant_index_map[variant_name];
+                alias_quotes.push(quote! { (#alias, #index) });
+                name_arm_quotes.push(quote! { #alias => Some(#index) });
+            }
+        }
+        for retired in parsed_attr.retired.iter() {
+            if taken_names.insert(retired.clone(), &input.ident).is_some() {
+                let message = format!(
+                    "The retired name `{}` is already the name of a variant, an alias or another retired variant",
+                    retired
+                );
+                return Err(quote_spanned! {input.ident.span()=>
+                    compile_error!(#message);
                 });
+            }
+        }
+
+        // The match is exhaustive unless the enum is remote or `#[non_exhaustive]`,
+        // the enum is local if the wildcard is unreachable.
+        if let Some(other_index) = other_index {
+            match_arm_quotes.push(quote! {
+                #[allow(unreachable_patterns)]
+                _ => Some(#other_index)
             });
-        ParsedEnum {
+        }
+
+        Ok(ParsedEnum {
+            target,
+            type_name,
             variant_count,
             variant_len,
             weights: weights
@@ -123,8 +383,19 @@ impl ParsedEnum {
                 .map(|weight| quote! { #weight })
                 .collect(),
             match_arm_quotes,
+            flag_quotes: None,
+            name_arm_quotes,
+            other_index,
+            variant_names,
+            ignored_names,
+            alias_quotes,
+            retired_names: parsed_attr.retired.clone(),
+            variant_groups,
+            group_names: parsed_attr.groups.keys().cloned().collect(),
+            expected_proportions,
             check_quotes,
             weighted_check_quotes,
+            proportion_check_quotes,
             erase_quotes,
             aggregate_quotes,
             group_aggregate_quotes: parsed_attr
@@ -141,6 +412,20 @@ impl ParsedEnum {
                     }
                 })
                 .collect(),
+            group_frequency_quotes: parsed_attr
+                .groups
+                .values()
+                .map(|idents| {
+                    let variant_quotes = idents
+                        .iter()
+                        .filter_map(|ident| variant_index_map.get(ident))
+                        .map(|index| quote! { self.frequency[#index] })
+                        .collect::<Vec<proc_macro2::TokenStream>>();
+                    quote! {
+                        #(#variant_quotes)+*
+                    }
+                })
+                .collect(),
             weighted_aggregate_quotes,
             weighted_group_aggregate_quotes: parsed_attr
                 .groups
@@ -157,6 +442,150 @@ impl ParsedEnum {
                 })
                 .collect(),
             has_customized_group: parsed_attr.has_customized_group,
+            derives: parsed_attr.derives.clone(),
+        })
+    }
+
+    /// Parse a struct, each `bool` or `Option` field acts as a variant.
+    pub(crate) fn parse_struct(
+        input: &DeriveInput,
+        fields: &FieldsNamed,
+        parsed_attr: &ParsedAttr,
+    ) -> Result<Self, proc_macro2::TokenStream> {
+        if let Some(ident) = parsed_attr
+            .tracks
+            .iter()
+            .chain(parsed_attr.predicates.keys())
+            .next()
+        {
+            return Err(quote_spanned! {ident.span()=>
+                compile_error!("#[counter(track)] and #[counter(when)] are not supported on struct fields.");
+            });
+        }
+
+        let mut flag_quotes = Vec::with_capacity(fields.named.len());
+        for field in fields.named.iter() {
+            let index = flag_quotes.len();
+            let ident = field
+                .ident
+                .as_ref()
+                .expect("named field must have an ident");
+            if parsed_attr.ignores.contains(ident) {
+                continue;
+            }
+            let flag = match flag_kind(&field.ty) {
+                Some(FlagKind::Bool) => quote! { target.#ident },
+                Some(FlagKind::Option) => quote! { target.#ident.is_some() },
+                None => {
+                    let message = format!(
+                        "Field `{}` is neither `bool` nor `Option`, please ignore it with #[counter(ignore)]",
+                        ident
+                    );
+                    return Err(quote_spanned! {field.ty.span()=>
+                        compile_error!(#message);
+                    });
+                }
+            };
+            flag_quotes.push(quote! {
+                if #flag { Some(#index) } else { None }
+            });
+        }
+
+        let mut parsed = Self::parse(input, &field_variants(fields), parsed_attr)?;
+        parsed.flag_quotes = Some(flag_quotes);
+        Ok(parsed)
+    }
+}
+
+/// Convert the fields of a struct into unit variants of the same name and attributes,
+/// so they can share the parsing of enum variants.
+pub(crate) fn field_variants(fields: &FieldsNamed) -> DataEnum {
+    DataEnum {
+        enum_token: Default::default(),
+        brace_token: fields.brace_token,
+        variants: fields
+            .named
+            .iter()
+            .map(|field| Variant {
+                attrs: field.attrs.clone(),
+                ident: field.ident.clone().expect("named field must have an ident"),
+                fields: Fields::Unit,
+                discriminant: None,
+            })
+            .collect(),
+    }
+}
+
+enum FlagKind {
+    Bool,
+    Option,
+}
+
+/// The kind of a field which can be counted, `bool` or `Option<T>`.
+fn flag_kind(ty: &syn::Type) -> Option<FlagKind> {
+    match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            let segment = type_path.path.segments.last()?;
+            if segment.ident == "bool" && segment.arguments.is_empty() {
+                Some(FlagKind::Bool)
+            } else if segment.ident == "Option" && !segment.arguments.is_empty() {
+                Some(FlagKind::Option)
+            } else {
+                None
+            }
+        }
+        syn::Type::Group(group) => flag_kind(&group.elem),
+        syn::Type::Paren(paren) => flag_kind(&paren.elem),
+        _ => None,
+    }
+}
+
+/// The match arm to map the display name or identifier of a variant to its slot.
+fn name_arm(
+    display_name: &str,
+    ident: &proc_macro2::Ident,
+    index: usize,
+) -> proc_macro2::TokenStream {
+    let ident = ident.to_string();
+    if display_name == ident {
+        quote! { #display_name => Some(#index) }
+    } else {
+        quote! { #display_name | #ident => Some(#index) }
+    }
+}
+
+/// The pattern to match the variant regardless of its fields.
+fn variant_pattern(name: &proc_macro2::TokenStream, variant: &Variant) -> proc_macro2::TokenStream {
+    let variant_name = &variant.ident;
+    match &variant.fields {
+        Fields::Named(_) => quote! { #name::#variant_name{ .. } },
+        Fields::Unnamed(f) if f.unnamed.is_empty() => quote! { #name::#variant_name() },
+        Fields::Unnamed(_) => quote! { #name::#variant_name(..) },
+        Fields::Unit => quote! { #name::#variant_name },
+    }
+}
+
+/// The pattern to bind all fields of the variant, along with the bindings in declaration order.
+fn variant_bindings(
+    name: &proc_macro2::TokenStream,
+    variant: &Variant,
+) -> (proc_macro2::TokenStream, Vec<proc_macro2::Ident>) {
+    let variant_name = &variant.ident;
+    match &variant.fields {
+        Fields::Named(fields) => {
+            let bindings = fields
+                .named
+                .iter()
+                .filter_map(|field| field.ident.clone())
+                .collect::<Vec<_>>();
+            (quote! { #name::#variant_name{ #(#bindings),* } }, bindings)
+        }
+        Fields::Unnamed(fields) => {
+            let bindings = (0..fields.unnamed.len())
+                .map(|index| format_ident!("__field{}", index))
+                .collect::<Vec<_>>();
+            (quote! { #name::#variant_name(#(#bindings),*) }, bindings)
         }
+        Fields::Unit => (quote! { #name::#variant_name }, vec![]),
     }
 }
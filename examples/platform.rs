@@ -30,4 +30,10 @@ fn main() {
     assert_eq!(counter.check_android(), 2);
     println!("{:?}", counter.aggregate());
     println!("{:?}", counter.group_aggregate());
+
+    // An aligned table of each variant.
+    println!("{}", counter);
+    // Group the rows, and limit each line to 50 characters.
+    println!("{:#50}", counter);
+    println!("{:#}", counter.weighted());
 }
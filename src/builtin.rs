@@ -0,0 +1,122 @@
+//! Ready-made counters for enums of the standard library, and `log::Level` with the `log` feature.
+//!
+//! ```rust
+//! use core::cmp::Ordering;
+//! use variant_counter::VariantCount;
+//!
+//! let mut counter = Ordering::counter();
+//! counter.record(&1.cmp(&2));
+//! counter.record(&2.cmp(&2));
+//! assert_eq!(counter.sum(), 2);
+//!
+//! let mut counter = Option::<u8>::counter();
+//! counter.record(&Some(1));
+//! counter.record(&None::<u8>);
+//! assert_eq!(counter.ranked().next(), Some(("None", 1)));
+//! ```
+
+use crate::{VariantCount, Variants};
+
+/// The mirror of [`core::cmp::Ordering`] to derive [`OrderingCounter`].
+#[doc(hidden)]
+#[derive(VariantCount)]
+#[counter(remote = "core::cmp::Ordering", exhaustive)]
+pub enum OrderingDef {
+    Less,
+    Equal,
+    Greater,
+}
+
+/// The mirror of [`core::option::Option`] to derive [`OptionCounter`].
+#[doc(hidden)]
+#[derive(VariantCount)]
+#[counter(remote = "core::option::Option", exhaustive)]
+pub enum OptionDef<T> {
+    None,
+    Some(T),
+}
+
+/// The mirror of [`core::result::Result`] to derive [`ResultCounter`].
+#[doc(hidden)]
+#[derive(VariantCount)]
+#[counter(remote = "core::result::Result", exhaustive)]
+pub enum ResultDef<T, E> {
+    Ok(T),
+    Err(E),
+}
+
+/// The mirror of [`log::Level`](::log::Level) to derive [`LevelCounter`].
+#[cfg(feature = "log")]
+#[doc(hidden)]
+#[derive(VariantCount)]
+#[counter(remote = "::log::Level", exhaustive)]
+pub enum LevelDef {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl VariantCount for core::cmp::Ordering {
+    type Counter = OrderingCounter;
+
+    fn counter() -> Self::Counter {
+        OrderingCounter::new()
+    }
+}
+
+impl<T> VariantCount for Option<T> {
+    type Counter = OptionCounter;
+
+    fn counter() -> Self::Counter {
+        OptionCounter::new()
+    }
+}
+
+#[cfg(feature = "log")]
+impl VariantCount for ::log::Level {
+    type Counter = LevelCounter;
+
+    fn counter() -> Self::Counter {
+        LevelCounter::new()
+    }
+}
+
+impl<T, E> VariantCount for Result<T, E> {
+    type Counter = ResultCounter;
+
+    fn counter() -> Self::Counter {
+        ResultCounter::new()
+    }
+}
+
+/// Forward the layout of a real type to its mirror, so it can be used in a `PairCounter`.
+macro_rules! forward_variants {
+    ($(#[$attr:meta])* impl<$($generic:ident),*> for $ty:ty => $mirror:ty) => {
+        $(#[$attr])*
+        impl<$($generic),*> Variants for $ty {
+            type Target = $ty;
+            type Array<U: Copy> = <$mirror as Variants>::Array<U>;
+
+            const VARIANT_NAMES: &'static [&'static str] = <$mirror as Variants>::VARIANT_NAMES;
+            const VARIANT_GROUPS: &'static [usize] = <$mirror as Variants>::VARIANT_GROUPS;
+            const GROUP_NAMES: &'static [&'static str] = <$mirror as Variants>::GROUP_NAMES;
+
+            #[inline]
+            fn array<U: Copy>(value: U) -> Self::Array<U> {
+                <$mirror as Variants>::array(value)
+            }
+
+            #[inline]
+            fn for_each_index(target: &Self::Target, f: impl FnMut(usize)) {
+                <$mirror as Variants>::for_each_index(target, f)
+            }
+        }
+    };
+}
+
+forward_variants!(impl<> for core::cmp::Ordering => OrderingDef);
+forward_variants!(impl<T> for Option<T> => OptionDef<T>);
+forward_variants!(impl<T, E> for Result<T, E> => ResultDef<T, E>);
+forward_variants!(#[cfg(feature = "log")] impl<> for ::log::Level => LevelDef);
@@ -0,0 +1,115 @@
+//! The changes between two counters used by the derived `diff()` method.
+
+use core::fmt;
+
+/// The change of a variant or group from the earlier counter to the later one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Change {
+    /// The name of the variant or group.
+    pub name: &'static str,
+    /// The count in the earlier counter.
+    pub before: usize,
+    /// The count in the later counter.
+    pub after: usize,
+}
+
+impl Change {
+    /// The signed difference of the counts.
+    #[inline]
+    pub const fn delta(&self) -> i128 {
+        self.after as i128 - self.before as i128
+    }
+
+    /// The difference relative to the earlier count, such as `0.5` for an increase of 50%.
+    ///
+    /// Returns `None` if the earlier count is zero.
+    #[inline]
+    pub fn relative(&self) -> Option<f64> {
+        if self.before == 0 {
+            None
+        } else {
+            Some(self.delta() as f64 / self.before as f64)
+        }
+    }
+
+    /// Whether the count has changed.
+    #[inline]
+    pub const fn is_changed(&self) -> bool {
+        self.before != self.after
+    }
+
+    /// Whether the count went from zero to non-zero.
+    #[inline]
+    pub const fn appeared(&self) -> bool {
+        self.before == 0 && self.after > 0
+    }
+
+    /// Whether the count went from non-zero to zero.
+    #[inline]
+    pub const fn vanished(&self) -> bool {
+        self.before > 0 && self.after == 0
+    }
+}
+
+/// Format the change, such as `Ok: 4 -> 6 (+2, +50.00%)`, `Timeout: 0 -> 3 (+3, new)` or `Retry: 2 -> 0 (-2, gone)`.
+///
+/// The precision of the formatter is respected, which is `2` by default.
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {} ({:+}",
+            self.name,
+            self.before,
+            self.after,
+            self.delta()
+        )?;
+        if self.appeared() {
+            f.write_str(", new)")
+        } else if self.vanished() {
+            f.write_str(", gone)")
+        } else {
+            let precision = f.precision().unwrap_or(2);
+            let relative = self.relative().unwrap_or(0.0) * 100.0;
+            write!(f, ", {:+.*}%)", precision, relative)
+        }
+    }
+}
+
+/// Pair the entries of the earlier and later counters, which are in the same order.
+pub fn changes<const N: usize>(
+    before: [(&'static str, usize); N],
+    after: [(&'static str, usize); N],
+) -> [Change; N] {
+    let mut changes = [Change {
+        name: "",
+        before: 0,
+        after: 0,
+    }; N];
+    for (change, ((name, before), (_, after))) in changes.iter_mut().zip(before.iter().zip(after)) {
+        *change = Change {
+            name,
+            before: *before,
+            after,
+        };
+    }
+    changes
+}
+
+/// Write the changed entries separated by commas, or `no change`.
+pub fn write_changes(f: &mut fmt::Formatter<'_>, changes: &[Change]) -> fmt::Result {
+    let mut changed = changes
+        .iter()
+        .filter(|change| change.is_changed())
+        .peekable();
+    if changed.peek().is_none() {
+        return f.write_str("no change");
+    }
+    for (index, change) in changed.enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+        fmt::Display::fmt(change, f)?;
+    }
+    Ok(())
+}
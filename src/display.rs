@@ -0,0 +1,200 @@
+//! Formatting helpers used by the derived `Display` implementations.
+
+use core::fmt;
+
+/// Get the share of `freq` in the `total`.
+///
+/// Returns `0.0` instead of `NaN` if the `total` is zero.
+#[inline]
+pub fn share(freq: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        freq as f64 / total as f64
+    }
+}
+
+/// Write the entries as percentages, such as `Android: 66.67%, IOS: 33.33%`.
+///
+/// The precision of the formatter is respected, which is `2` by default.
+pub fn write_shares(
+    f: &mut fmt::Formatter<'_>,
+    entries: &[(&'static str, usize)],
+    total: usize,
+) -> fmt::Result {
+    let precision = f.precision().unwrap_or(2);
+    for (index, (name, freq)) in entries.iter().enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+        write!(
+            f,
+            "{}: {:.*}%",
+            name,
+            precision,
+            share(*freq, total) * 100.0
+        )?;
+    }
+    Ok(())
+}
+
+/// The bar width if the formatter has no width.
+const DEFAULT_BAR_WIDTH: usize = 20;
+/// The eighth blocks to draw the fractional part of a bar.
+const BAR_EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// An aligned table of variant, count, percent and a unicode bar.
+///
+/// The width of the formatter, such as `{:60}`, limits the width of each line,
+/// the bar takes the rest of the line after the other columns.
+/// The alternate form `{:#}` groups rows under their group if `group_names` is not empty.
+#[derive(Debug, Clone, Copy)]
+pub struct Table<'a> {
+    /// The name and value of each row.
+    pub entries: &'a [(&'static str, usize)],
+    /// The group index of each row in `group_names`.
+    pub groups: &'a [usize],
+    /// The name of each group, the rows are not grouped if it is empty.
+    pub group_names: &'a [&'static str],
+}
+
+impl<'a> Table<'a> {
+    fn total(&self) -> usize {
+        self.entries.iter().map(|(_, value)| value).sum()
+    }
+
+    fn group_total(&self, group: usize) -> usize {
+        self.group_rows(group).map(|(_, value)| value).sum()
+    }
+
+    fn group_rows(&self, group: usize) -> impl Iterator<Item = &(&'static str, usize)> + '_ {
+        self.entries
+            .iter()
+            .zip(self.groups)
+            .filter(move |(_, g)| **g == group)
+            .map(|(entry, _)| entry)
+    }
+
+    /// Write the table.
+    pub fn write(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let grouped = f.alternate() && !self.group_names.is_empty();
+        let total = self.total();
+
+        // The widest row decides the width of each column.
+        let indent = if grouped { 2 } else { 0 };
+        let mut name_width = self
+            .entries
+            .iter()
+            .map(|(name, _)| name.chars().count() + indent)
+            .max()
+            .unwrap_or(0);
+        let mut max_value = self
+            .entries
+            .iter()
+            .map(|(_, value)| *value)
+            .max()
+            .unwrap_or(0);
+        if grouped {
+            for (group, name) in self.group_names.iter().enumerate() {
+                name_width = name_width.max(name.chars().count());
+                max_value = max_value.max(self.group_total(group));
+            }
+        }
+        let value_width = digits(max_value.max(total));
+        // `100.00%`
+        let percent_width = 7;
+        let bar_width = match f.width() {
+            Some(width) => width.saturating_sub(name_width + value_width + percent_width + 6),
+            None => DEFAULT_BAR_WIDTH,
+        };
+
+        let row = Row {
+            name_width,
+            value_width,
+            percent_width,
+            bar_width,
+            max_value,
+            total,
+        };
+        if grouped {
+            for (group, group_name) in self.group_names.iter().enumerate() {
+                row.write(f, "", group_name, self.group_total(group))?;
+                let mut rows = self.group_rows(group).peekable();
+                // An ungrouped variant is a group of its own, no need to repeat it.
+                if let Some((name, _)) = rows.peek() {
+                    if name == group_name && self.group_rows(group).count() == 1 {
+                        continue;
+                    }
+                }
+                for (name, value) in rows {
+                    row.write(f, "  ", name, *value)?;
+                }
+            }
+        } else {
+            for (name, value) in self.entries {
+                row.write(f, "", name, *value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Row {
+    name_width: usize,
+    value_width: usize,
+    percent_width: usize,
+    bar_width: usize,
+    max_value: usize,
+    total: usize,
+}
+
+impl Row {
+    fn write(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        indent: &str,
+        name: &str,
+        value: usize,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "{}{:<name_width$}  {:>value_width$}  {:>percent_width$.2}%",
+            indent,
+            name,
+            value,
+            share(value, self.total) * 100.0,
+            name_width = self.name_width - indent.len(),
+            value_width = self.value_width,
+            percent_width = self.percent_width - 1,
+        )?;
+        write_bar(f, share(value, self.max_value), self.bar_width)?;
+        f.write_str("\n")
+    }
+}
+
+/// Write a bar filling `ratio` of the `width` in eighths of a character,
+/// it writes nothing if the bar is empty.
+fn write_bar(f: &mut fmt::Formatter<'_>, ratio: f64, width: usize) -> fmt::Result {
+    // Round half up without `f64::round`, which is not available in `core`.
+    let eighths = (ratio * (width * 8) as f64 + 0.5) as usize;
+    if eighths > 0 {
+        f.write_str("  ")?;
+    }
+    for _ in 0..eighths / 8 {
+        f.write_str("█")?;
+    }
+    let remainder = eighths % 8;
+    if remainder > 0 {
+        write!(f, "{}", BAR_EIGHTHS[remainder])?;
+    }
+    Ok(())
+}
+
+fn digits(mut value: usize) -> usize {
+    let mut digits = 1;
+    while value >= 10 {
+        value /= 10;
+        digits += 1;
+    }
+    digits
+}
@@ -0,0 +1,158 @@
+//! A `tracing-subscriber` [`Layer`] which counts the events by an enum field.
+//!
+//! ```rust
+//! # use variant_counter::{layer::CounterLayer, VariantCount};
+//! use tracing_subscriber::layer::SubscriberExt;
+//!
+//! #[derive(Debug, VariantCount)]
+//! enum EventKind {
+//!     Login,
+//!     Logout,
+//! }
+//!
+//! let layer = CounterLayer::new("kind", EventKind::counter());
+//! let counter = layer.counter();
+//! let subscriber = tracing_subscriber::registry().with(layer);
+//! tracing::subscriber::with_default(subscriber, || {
+//!     tracing::info!(kind = ?EventKind::Login, "login");
+//! });
+//! assert_eq!(counter.lock().sum(), 1);
+//! ```
+
+use core::fmt::{self, Write};
+
+use ::tracing::{
+    field::{Field, Visit},
+    Event, Subscriber,
+};
+use ::tracing_subscriber::layer::{Context, Layer};
+
+use crate::{shared::SharedCounter, NamedCounter};
+
+/// A [`Layer`] which records the value of the `field` of each event into the counter,
+/// if the value is the name of a variant.
+///
+/// The value is recorded by [`NamedCounter::record_name`], either as a string
+/// or formatted by `Debug`, such as `kind = "Login"`, `kind = %kind` or `kind = ?kind`.
+/// The fields of a variant are skipped, so `Ping(1)` is recorded as `Ping`.
+#[derive(Debug)]
+pub struct CounterLayer<C> {
+    field: &'static str,
+    counter: SharedCounter<C>,
+}
+
+impl<C> CounterLayer<C> {
+    /// Count the events by the `field`.
+    pub fn new(field: &'static str, counter: C) -> Self {
+        Self::with_shared(field, SharedCounter::new(counter))
+    }
+
+    /// Count the events by the `field` into a shared counter.
+    pub fn with_shared(field: &'static str, counter: SharedCounter<C>) -> Self {
+        CounterLayer { field, counter }
+    }
+
+    /// Get the handle to the live counter.
+    pub fn counter(&self) -> SharedCounter<C> {
+        self.counter.clone()
+    }
+}
+
+impl<S, C> Layer<S> for CounterLayer<C>
+where
+    S: Subscriber,
+    C: NamedCounter + Send + 'static,
+{
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if event.metadata().fields().field(self.field).is_none() {
+            return;
+        }
+        event.record(&mut FieldVisitor {
+            field: self.field,
+            counter: &self.counter,
+        });
+    }
+}
+
+struct FieldVisitor<'a, C> {
+    field: &'static str,
+    counter: &'a SharedCounter<C>,
+}
+
+impl<C: NamedCounter> Visit for FieldVisitor<'_, C> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == self.field {
+            self.counter.lock().record_name(variant_name(value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == self.field {
+            let mut path = PathBuffer::new();
+            // The formatting stops with an error after the path.
+            let _ = write!(path, "{:?}", value);
+            if !path.overflowed {
+                self.counter.lock().record_name(variant_name(path.as_str()));
+            }
+        }
+    }
+}
+
+/// The capacity of [`PathBuffer`], longer paths can't be recorded.
+const PATH_CAPACITY: usize = 128;
+
+/// A buffer on the stack which keeps the leading path of a formatted value,
+/// such as `Kind::Ping` of `Kind::Ping(1)`, so the events are counted without allocation.
+struct PathBuffer {
+    bytes: [u8; PATH_CAPACITY],
+    len: usize,
+    overflowed: bool,
+}
+
+impl PathBuffer {
+    fn new() -> Self {
+        PathBuffer {
+            bytes: [0; PATH_CAPACITY],
+            len: 0,
+            overflowed: false,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
+
+impl Write for PathBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if self.len == 0 && c.is_whitespace() {
+                continue;
+            }
+            if !is_path_char(c) {
+                return Err(fmt::Error);
+            }
+            let end = self.len + c.len_utf8();
+            if end > PATH_CAPACITY {
+                self.overflowed = true;
+                return Err(fmt::Error);
+            }
+            c.encode_utf8(&mut self.bytes[self.len..end]);
+            self.len = end;
+        }
+        Ok(())
+    }
+}
+
+fn is_path_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == ':'
+}
+
+/// Get the variant name of a formatted value, such as `Ping` of `Kind::Ping(1)`.
+fn variant_name(value: &str) -> &str {
+    let value = value.trim();
+    let end = value
+        .find(|c: char| !is_path_char(c))
+        .unwrap_or(value.len());
+    value[..end].rsplit("::").next().unwrap_or_default()
+}
@@ -34,6 +34,19 @@
 //!
 //! Those `erase_*()` methods are under `erase` feature flag, and disabled by default.
 //!
+//! To erase by value, use `erase()` and `erase_n()` which saturate at zero,
+//! or `try_erase()` and `try_erase_n()` which return an `UnderflowError` without erasing instead.
+//!
+//! ```rust,ignore
+//! counter.erase(&Enum::Variant1);
+//! counter.erase_n(&Enum::Variant1, 2);
+//!
+//! if let Err(error) = counter.try_erase(&Enum::Variant1) {
+//!     // cannot erase 1 record(s) of `Variant1` which has 0
+//!     println!("{}", error);
+//! }
+//! ```
+//!
 //! ### Check the record with `check_*()` methods
 //!
 //! ```rust,ignore
@@ -75,6 +88,143 @@
 //! counter.record(&Level::Trace);
 //! ```
 //!
+//! Use `#[counter(ignore, track)]` to keep counting an ignored variant separately,
+//! which is not included in `sum()` or any statistics.
+//!
+//! ```rust,ignore
+//! #[derive(VariantCount)]
+//! pub enum Event {
+//!   #[counter(ignore, track)]
+//!   Heartbeat,
+//!   Login,
+//! }
+//!
+//! counter.record(&Event::Heartbeat);
+//! assert_eq!(counter.sum(), 0);
+//! assert_eq!(counter.ignored_count(), 1);
+//! let ignored = counter.ignored_aggregate();
+//! ```
+//!
+//! ### Record conditionally
+//!
+//! Use `#[counter(when = "path::to::predicate")]` to record a variant only if its fields satisfy the predicate,
+//! which takes a reference of each field in declaration order.
+//! The variant is ignored if the predicate fails, or recorded as the `fallback` variant if declared.
+//! Erasing and discarding follow the predicate as well, so they affect the `fallback` variant if the predicate fails:
+//! `discard()` clears every record of the `fallback` variant, not only those which have fallen back.
+//!
+//! ```rust
+//! # use variant_counter::VariantCount;
+//! fn is_server_error(status: &u16) -> bool {
+//!     *status >= 500
+//! }
+//!
+//! #[derive(VariantCount)]
+//! pub enum Event {
+//!   #[counter(when = "is_server_error", fallback = "Other")]
+//!   Response(u16),
+//!   Other,
+//! }
+//! ```
+//!
+//! ### Rename a variant
+//!
+//! The `check_*()` and `erase_*()` methods are named after the variant in snake case,
+//! such as `check_chrome_os()` for `ChromeOS`. Variants generating the same method name are rejected,
+//! use `#[counter(rename = "...")]` to override both the method name and the name used in `aggregate()`.
+//!
+//! ```rust
+//! # use variant_counter::VariantCount;
+//! #[derive(VariantCount)]
+//! pub enum Platform {
+//!   ChromeOS,
+//!   IOS,
+//!   // `check_ios_legacy()`, and `"ios_legacy"` in `aggregate()`.
+//!   #[counter(rename = "ios_legacy")]
+//!   Ios,
+//! }
+//! ```
+//!
+//! Use `#[counter(rename_all = "...")]` on the enum to rename all variants and groups in the style of serde,
+//! the rule can be one of `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`, `"camelCase"`, `"snake_case"`,
+//! `"SCREAMING_SNAKE_CASE"`, `"kebab-case"` and `"SCREAMING-KEBAB-CASE"`.
+//! It applies to all aggregates, but not to the method names.
+//!
+//! ```rust
+//! # use variant_counter::VariantCount;
+//! #[derive(VariantCount)]
+//! #[counter(rename_all = "snake_case")]
+//! pub enum Platform {
+//!   // `"chrome_os"` in `aggregate()`.
+//!   ChromeOS,
+//!   // `"mobile_os"` in `group_aggregate()`.
+//!   #[counter(group = "MobileOS")]
+//!   Android,
+//! }
+//! ```
+//!
+//! ### Count struct flags
+//!
+//! `#[derive(VariantCount)]` also works on structs with named fields, each `bool` or `Option` field acts as a variant.
+//! `record()` records each field which is `true` or `Some`, other fields must be ignored by `#[counter(ignore)]`.
+//! The field attributes are the same as variants, except `track` and `when`.
+//!
+//! ```rust
+//! # use variant_counter::VariantCount;
+//! #[derive(VariantCount)]
+//! pub struct Request {
+//!   #[counter(group = "Auth")]
+//!   authenticated: bool,
+//!   #[counter(group = "Auth")]
+//!   is_admin: bool,
+//!   referrer: Option<String>,
+//!   #[counter(ignore)]
+//!   path: String,
+//! }
+//!
+//! let mut counter = Request::counter();
+//! counter.record(&Request {
+//!   authenticated: true,
+//!   is_admin: false,
+//!   referrer: Some("https://example.com".to_owned()),
+//!   path: "/".to_owned(),
+//! });
+//! assert_eq!(counter.sum(), 2);
+//! ```
+//!
+//! ### Count foreign enums
+//!
+//! Use `#[counter(remote = "path::to::Enum")]` on a mirror enum to count an enum defined in another crate, in the style of serde.
+//! The mirror declares the variants of the foreign enum which you are interested in.
+//! The counter records the foreign enum directly and is named after it, such as `ErrorKindCounter`.
+//!
+//! ```rust
+//! # use variant_counter::VariantCount;
+//! #[derive(VariantCount)]
+//! #[counter(remote = "std::io::ErrorKind")]
+//! enum ErrorKindDef {
+//!   NotFound,
+//!   PermissionDenied,
+//!   TimedOut,
+//! }
+//!
+//! let mut counter = ErrorKindDef::counter();
+//! counter.record(&std::io::ErrorKind::NotFound);
+//! ```
+//!
+//! Variants unknown to the mirror, or added to a `#[non_exhaustive]` enum, are recorded in the other bucket,
+//! which is named `"__other"` in the aggregates or declared by `#[counter(other = "...")]`, see `other_count()`.
+//! Use `#[counter(remote = "...", exhaustive)]` instead if the mirror declares all variants,
+//! then it is a compile error if the foreign enum gains a variant.
+//!
+//! The counters of `Ordering`, `Option` and `Result` are ready-made in the `builtin` module.
+//!
+//! ```rust
+//! # use variant_counter::VariantCount;
+//! let mut counter = std::cmp::Ordering::counter();
+//! counter.record(&1.cmp(&2));
+//! ```
+//!
 //! ### Aggregate your data
 //!
 //! ```rust,ignore
@@ -106,6 +256,105 @@
 //! // Group version of aggregate method
 //! let group_data = counter.group_aggregate();
 //! ```
+//! ### Display
+//!
+//! ```rust,ignore
+//! // An aligned table of variant, count, percent and bar.
+//! println!("{}", counter);
+//! // Group the rows under their group, and limit each line to 60 characters.
+//! println!("{:#60}", counter);
+//! ```
+//!
+//! ```text
+//! Desktop     1   20.00%  ████████▋
+//!   Windows   1   20.00%  ████████▋
+//!   Linux     0    0.00%
+//! Mobile      3   60.00%  ██████████████████████████
+//!   Android   2   40.00%  █████████████████▍
+//!   IOS       1   20.00%  ████████▋
+//! Others      1   20.00%  ████████▋
+//! ```
+//!
+//! The weighted counter has the same `Display` implementation.
+//!
+//! ### Proportions
+//!
+//! ```rust,ignore
+//! let proportions = counter.proportions();
+//!
+//! // The share of each variant, or each group, in the total frequency.
+//! proportions.aggregate();
+//! proportions.group_aggregate();
+//!
+//! // Android: 50.00%, IOS: 25.00%, ...
+//! println!("{}", proportions);
+//! // Formats the group shares with one decimal place: Desktop: 25.0%, Mobile: 75.0%, ...
+//! println!("{:#.1}", proportions);
+//! ```
+//!
+//! All the shares are `0.0` rather than `NaN` if nothing has been recorded.
+//!
+//! ### Diff
+//!
+//! ```rust,ignore
+//! let diff = before.diff(&after);
+//!
+//! // The change of each variant, or each group, and of the total.
+//! diff.variants();
+//! diff.groups();
+//! diff.total();
+//! // The variants which went from zero to non-zero, or back.
+//! diff.appeared();
+//! diff.vanished();
+//!
+//! let change = diff.get("Android").unwrap();
+//! (change.delta(), change.relative());
+//!
+//! // Android: 4 -> 6 (+2, +50.00%), IOS: 0 -> 3 (+3, new), ...
+//! println!("{}", diff);
+//! // Formats the changed groups instead: Mobile: 4 -> 9 (+5, +125.00%), ...
+//! println!("{:#}", diff);
+//! ```
+//!
+//! `diff()` compares with a later counter, such as the one after a deploy. Only the changed variants are formatted,
+//! or `no change`.
+//!
+//! ### Ranking
+//!
+//! ```rust,ignore
+//! // The 5 most frequent variants, and the rarest one.
+//! counter.top_k(5);
+//! counter.bottom_k(1);
+//!
+//! // All variants from the most frequent to the rarest.
+//! counter.ranked();
+//!
+//! // The recorded variants in declaration order.
+//! counter.nonzero();
+//!
+//! // Group versions.
+//! counter.group_top_k(1);
+//! ```
+//!
+//! Those methods return iterators over fixed arrays, so they don't allocate even in `no_std`.
+//! They are also available on the weighted counter.
+//!
+//! ### Derives
+//!
+//! The counter struct implements `Debug`, `Clone`, `Copy`, `Default`, `PartialEq`, `Eq` and `Hash`,
+//! the weighted counter implements `Debug`, `Clone`, `Copy` and `PartialEq`.
+//! You can forward extra derives to the counter struct with `#[counter(derive(...))]`:
+//!
+//! ```rust
+//! # use variant_counter::VariantCount;
+//! #[derive(VariantCount)]
+//! #[counter(derive(PartialOrd, Ord))]
+//! pub enum Status {
+//!   Ok,
+//!   Err,
+//! }
+//! ```
+//!
 //! ### Statistics
 //!
 //! ```rust,ignore
@@ -122,6 +371,51 @@
 //! counter.sd();
 //! ```
 //!
+//! ### Information-theoretic statistics
+//!
+//! ```rust,ignore
+//! // Shannon entropy in bits, and normalized to [0, 1]
+//! counter.entropy();
+//! counter.normalized_entropy();
+//!
+//! // Gini impurity
+//! counter.gini_impurity();
+//!
+//! // Divergence from another counter of the same enum
+//! today.kl_divergence(&yesterday);
+//! today.js_divergence(&yesterday);
+//! ```
+//!
+//! The group versions, such as `group_entropy()` and `group_js_divergence()`,
+//! are available if you have grouped variants.
+//!
+//! ### Goodness-of-fit
+//!
+//! ```rust
+//! # use variant_counter::VariantCount;
+//! #[derive(VariantCount)]
+//! enum Rollout {
+//!   #[counter(expected = 0.1)]
+//!   Enabled,
+//!   // Variants without `expected` share the remaining proportion evenly.
+//!   Disabled,
+//! }
+//!
+//! let mut counter = Rollout::counter();
+//! counter.record(&Rollout::Enabled);
+//!
+//! # #[cfg(feature = "stats")] {
+//! // Chi-square test against the declared expected proportions.
+//! let result = counter.chi_square();
+//! println!("{} {} {}", result.statistic, result.degrees_of_freedom, result.p_value);
+//! println!("{:?}", result.most_deviating(1));
+//!
+//! // Chi-square test against the uniform distribution, or any expected proportions.
+//! counter.chi_square_uniform();
+//! counter.chi_square_with(&[0.5, 0.5]);
+//! # }
+//! ```
+//!
 //! ### Weighted
 //!
 //! ```rust
@@ -148,6 +442,7 @@
 //! // Sum
 //! w.sum();
 //!
+//! # #[cfg(feature = "stats")] {
 //! // Average
 //! w.avg();
 //!
@@ -156,8 +451,256 @@
 //!
 //! // Standard deviation
 //! w.sd();
+//! # }
+//! ```
+//!
+//!
+//! ### Binary snapshot
+//!
+//! `to_bytes()` encodes the records into a compact binary snapshot with varint counts and zero-run compression,
+//! and `from_bytes()` decodes it. The header holds a format version and `SCHEMA_HASH`, the hash of the variant names in order,
+//! so decoding fails with `SnapshotError::SchemaMismatch` if the variants have changed.
+//! Encode with `Encoding { by_name: true, .. }` to map the records by variant name instead,
+//! which still decodes after variants are added, removed or reordered. See the `snapshot` module for the format.
+//!
+//! ```rust
+//! # use variant_counter::VariantCount;
+//! #[derive(VariantCount)]
+//! enum Status {
+//!   Ok,
+//!   NotFound,
+//! }
+//!
+//! # #[cfg(feature = "std")] {
+//! let mut counter = Status::counter();
+//! counter.record(&Status::Ok);
+//!
+//! let bytes = counter.to_bytes();
+//! assert_eq!(StatusCounter::from_bytes(&bytes), Ok(counter));
+//! # }
+//! ```
+//!
+//! ### Schema evolution
+//!
+//! Positional snapshots only decode with the same variants, so encode in by-name mode if the variants may change.
+//! Use `#[counter(alias = "...")]` on a renamed variant to map its previous names, it can be repeated,
+//! and `#[counter(retired = "...")]` on the enum to declare the names of removed variants.
+//! `load_bytes()` returns the loaded counter along with the dropped counts of the names which map to no variant,
+//! each marked whether it is retired or unknown. The aliases are also accepted by `record_name()`.
+//!
+//! ```rust
+//! # use variant_counter::VariantCount;
+//! # #[cfg(all(feature = "std", feature = "check"))] {
+//! use variant_counter::snapshot::Encoding;
+//!
+//! mod old {
+//!   # use variant_counter::VariantCount;
+//!   #[derive(VariantCount)]
+//!   pub enum Status {
+//!     Ok,
+//!     Missing,
+//!     Timeout,
+//!   }
+//! }
+//!
+//! #[derive(VariantCount)]
+//! #[counter(retired = "Timeout")]
+//! enum Status {
+//!   Ok,
+//!   #[counter(alias = "Missing")]
+//!   NotFound,
+//! }
+//!
+//! let mut old_counter = old::Status::counter();
+//! old_counter.record(&old::Status::Missing);
+//! old_counter.record(&old::Status::Missing);
+//! old_counter.record(&old::Status::Timeout);
+//!
+//! let bytes = old_counter.to_bytes_with(Encoding { by_name: true, ..Encoding::default() });
+//! let loaded = StatusCounter::load_bytes(&bytes).unwrap();
+//! // The counts of `Missing` are loaded into `NotFound`.
+//! assert_eq!(loaded.counter.check_not_found(), 2);
+//! assert_eq!(loaded.dropped[0].name, "Timeout");
+//! assert!(loaded.dropped[0].retired);
+//! # }
+//! ```
+//!
+//! ### CSV and TSV
+//!
+//! `write_csv()` writes the records as `variant,group,count,weighted,percent` rows under a header row,
+//! in `Format::Csv` or `Format::Tsv`. `write_csv_table()` writes several counters, such as hourly snapshots,
+//! one row per counter under a header of `VARIANT_NAMES`. `read_csv()` and `read_csv_table()` read them back
+//! by the header and map the names like `load_bytes()`, so the aliases apply and the unknown names are reported.
+//!
+//! ```rust
+//! # use variant_counter::VariantCount;
+//! # #[cfg(feature = "std")] {
+//! use variant_counter::table::Format;
+//!
+//! #[derive(VariantCount)]
+//! enum Status {
+//!   Ok,
+//!   #[counter(weight = 2)]
+//!   NotFound,
+//! }
+//!
+//! let mut counter = Status::counter();
+//! counter.record(&Status::Ok);
+//! counter.record(&Status::NotFound);
+//! counter.record(&Status::NotFound);
+//! counter.record(&Status::Ok);
+//!
+//! let mut csv = Vec::new();
+//! counter.write_csv(&mut csv, Format::Csv).unwrap();
+//! assert_eq!(
+//!   String::from_utf8(csv.clone()).unwrap(),
+//!   "variant,group,count,weighted,percent\nOk,Ok,2,2,50.00\nNotFound,NotFound,2,4,50.00\n"
+//! );
+//!
+//! let loaded = StatusCounter::read_csv(&csv[..], Format::Csv).unwrap();
+//! assert_eq!(loaded.counter, counter);
+//!
+//! let mut table = Vec::new();
+//! StatusCounter::write_csv_table(&mut table, Format::Csv, [&counter, &StatusCounter::new()]).unwrap();
+//! assert_eq!(String::from_utf8(table).unwrap(), "Ok,NotFound\n2,2\n0,0\n");
+//! # }
+//! ```
+//!
+//! ### Static counters
+//!
+//! `{Enum}StaticCounter` can be recorded by a shared reference, so it works in a `static` item without locks.
+//! Each count is an atomic, or is protected by a critical section on targets without atomics,
+//! which requires an implementation of the [critical-section](https://crates.io/crates/critical-section) crate.
+//! Use `snapshot()` to get a normal counter for aggregates and statistics.
+//!
+//! ```rust
+//! # use variant_counter::VariantCount;
+//! #[derive(VariantCount)]
+//! enum ErrorKind {
+//!   NotFound,
+//!   Timeout,
+//! }
+//!
+//! static ERRORS: ErrorKindStaticCounter = ErrorKindStaticCounter::new();
+//!
+//! ERRORS.record(&ErrorKind::Timeout);
+//! assert_eq!(ERRORS.snapshot().sum(), 1);
+//! ```
+//!
+//! `{Enum}ShardedCounter` records into the shard of the current thread instead, each shard is padded to its own cache line,
+//! which avoids the contention of many threads recording the same variant. The shards are combined on `snapshot()`,
+//! so the reads are eventually consistent with the records. It has 16 shards by default,
+//! use `{Enum}ShardedCounter::<64>::with_shards()` for more. Compare them by `cargo bench --bench record`.
+//!
+//! ### Take and report periodically
+//!
+//! `take()` returns the records and resets them in one step. It is atomic per count on `{Enum}StaticCounter`
+//! and `{Enum}ShardedCounter`, and under the lock on `SharedCounter`, so no record is lost or counted twice
+//! between a snapshot and a reset. The `report::Reporter` takes the records once per interval and hands them to a `Sink`,
+//! which is any closure taking a `Report`. The time comes from a `Clock`, use `ManualClock` to test without real time.
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//! use variant_counter::report::{Report, Reporter, SystemClock};
+//!
+//! static ERRORS: ErrorKindStaticCounter = ErrorKindStaticCounter::new();
+//!
+//! let mut reporter = Reporter::new(
+//!   &ERRORS,
+//!   SystemClock::new(),
+//!   |report: Report<ErrorKindCounter>| println!("{:?}: {}", report.end, report.counter),
+//!   Duration::from_secs(10),
+//! );
+//! // Report every 10 seconds until the shutdown.
+//! reporter.run(|| !shutting_down());
+//! ```
+//!
+//! ### Sampling
+//!
+//! With the `std` feature, `{Enum}SampledCounter` records only a sample of the values for the hot paths where counting every value costs too much,
+//! either one in every `n` values or each value with a probability. The random number generator is seeded,
+//! or injected by `with_rng()`, so the sampling is reproducible. `aggregate()` and `sum()` scale the counts back up,
+//! and `estimates()` gives the confidence interval of each variant. `sampled()` is the normal counter of the sampled values.
+//!
+//! ```rust
+//! # use variant_counter::VariantCount;
+//! # #[cfg(feature = "std")] {
+//! use variant_counter::sample::Sampling;
+//!
+//! #[derive(VariantCount)]
+//! enum Level {
+//!   Debug,
+//!   Info,
+//! }
+//!
+//! let mut counter = LevelSampledCounter::new(Sampling::Probability(0.01), 42);
+//! for _ in 0..100_000 {
+//!   counter.record(&Level::Debug);
+//! }
+//!
+//! let [debug, _] = counter.estimates(0.99);
+//! assert!(debug.low <= 100_000.0 && 100_000.0 <= debug.high);
+//! # }
+//! ```
+//!
+//! ### Pairs and transitions
+//!
+//! `pair::PairCounter<A, B>` counts the pairs of two derived types, such as `(Platform, Level)`, in a `[[usize; M]; N]` matrix.
+//! It has the marginals of the rows and the columns, and with the `std` feature, of the groups too and a contingency table.
+//! `pair::TransitionCounter<E>` counts the transitions between consecutive values of a stream, such as the states of a state machine.
+//!
+//! ```rust
+//! # use variant_counter::VariantCount;
+//! use variant_counter::pair::{PairCounter, TransitionCounter};
+//!
+//! #[derive(VariantCount)]
+//! enum Level {
+//!   Info,
+//!   Error,
+//! }
+//!
+//! let mut pairs = PairCounter::<Option<u8>, Level>::new();
+//! pairs.record(&Some(1), &Level::Info);
+//! pairs.record(&None, &Level::Error);
+//! pairs.record(&Some(2), &Level::Error);
+//! assert_eq!(pairs.matrix(), &[[0, 1], [1, 1]]);
+//! assert_eq!(pairs.column_marginals(), [1, 2]);
+//! //       Info Error total
+//! // None     0     1     1
+//! // Some     1     1     2
+//! // total    1     2     3
+//! # #[cfg(feature = "std")]
+//! println!("{}", pairs);
+//!
+//! let mut transitions = TransitionCounter::<Level>::new();
+//! transitions.record_all(&[Level::Info, Level::Error, Level::Error]);
+//! assert_eq!(transitions.pairs().get("Error", "Error"), Some(1));
 //! ```
 //!
+//! ### Count `tracing` events and `log` records
+//!
+//! With the `tracing` feature, `layer::CounterLayer` is a `tracing-subscriber` layer which records the value of an event field,
+//! if the value is the name of a variant. The counter is shared by a `shared::SharedCounter` handle.
+//!
+//! ```rust,ignore
+//! use tracing_subscriber::layer::SubscriberExt;
+//! use variant_counter::layer::CounterLayer;
+//!
+//! #[derive(Debug, VariantCount)]
+//! enum EventKind {
+//!   Login,
+//!   Logout,
+//! }
+//!
+//! let layer = CounterLayer::new("kind", EventKind::counter());
+//! let counter = layer.counter();
+//! tracing::subscriber::set_global_default(tracing_subscriber::registry().with(layer)).unwrap();
+//!
+//! tracing::info!(kind = ?EventKind::Login, "user logged in");
+//! assert_eq!(counter.lock().sum(), 1);
+//! ```
+//!
+//! With the `log` feature, `logger::CountingLogger` counts the records by `log::Level`, then forwards them to the inner logger.
 //!
 //! ## Macro expand
 //!
@@ -240,18 +783,52 @@
 //! 
 //! ## Feature flags
 //! 
-//! - `full`: Enable all features.
+//! - `full`: Enable all features except the `tracing` and `log` integrations.
 //! 
 //! - `check`: Generate `check` methods for variants.
 //! 
 //! - `erase`: Generate `erase` methods for variants.
 //!
-//! - `stats`: Generate statistics methods, such as `avg()`, `variance()`, and `sd()`, etc.
+//! - `stats`: Generate statistics methods, such as `avg()`, `variance()`, `sd()` and `entropy()`, etc.
 //! 
+//! - `tracing`: Enable the `tracing-subscriber` layer in `layer` module.
+//!
+//! - `log`: Enable the `log` adapter in `logger` module, and the `LevelCounter` of `log::Level`.
+//!
 //! - `std`: Enable `std` crate supported. Enabled by default. Please disable this feature to support `no_std`.
+//!   The `shared`, `report`, `table` and `sample` modules require it, and the `stats`, `tracing` and `log` features enable it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::fmt;
 
 pub use variant_counter_derived::*;
 
+// The derived code refers to `variant_counter`, which is the crate itself in `builtin`.
+extern crate self as variant_counter;
+
+pub mod builtin;
+pub mod diff;
+pub mod display;
+#[cfg(feature = "tracing")]
+pub mod layer;
+#[cfg(feature = "log")]
+pub mod logger;
+pub mod pair;
+pub mod rank;
+#[cfg(feature = "std")]
+pub mod report;
+#[cfg(feature = "std")]
+pub mod sample;
+#[cfg(feature = "std")]
+pub mod shared;
+pub mod snapshot;
+#[cfg(feature = "stats")]
+pub mod stats;
+pub mod sync;
+#[cfg(feature = "std")]
+pub mod table;
+
 /// The core `VariantCount` trait which provides an accosiated `counter()` method
 /// to get the concrete counter type.
 ///
@@ -275,3 +852,59 @@ pub trait VariantCount {
     /// The accosiated function to get the concrete counter type.
     fn counter() -> Self::Counter;
 }
+
+/// A counter which records a variant by its name, which is either the name in `aggregate()`
+/// or the identifier of the variant. It is implemented by the derived counters.
+pub trait NamedCounter {
+    /// Record the variant of the `name`, returns `false` if there is no such variant
+    /// or the variant is ignored.
+    fn record_name(&mut self, name: &str) -> bool;
+}
+
+/// The layout of the variants which are not ignored, along with the slot of a value.
+/// It is implemented by the derived types, and used by the counters of two types,
+/// such as [`PairCounter`](pair::PairCounter).
+pub trait Variants {
+    /// The recorded type, which is the remote type declared by `#[counter(remote = "...")]` or the type itself.
+    type Target: ?Sized;
+    /// An array of `T` with an element for each variant, which is `[T; N]`.
+    type Array<T: Copy>: AsRef<[T]> + AsMut<[T]> + Copy;
+
+    /// The name of each variant, the same as `{Enum}Counter::VARIANT_NAMES`.
+    const VARIANT_NAMES: &'static [&'static str];
+    /// The group index of each variant, the same as `{Enum}Counter::VARIANT_GROUPS`.
+    const VARIANT_GROUPS: &'static [usize];
+    /// The name of each group, the same as `{Enum}Counter::GROUP_NAMES`.
+    const GROUP_NAMES: &'static [&'static str];
+
+    /// Create an array filled with the value.
+    fn array<T: Copy>(value: T) -> Self::Array<T>;
+
+    /// Call `f` with the index of each variant the target is recorded to,
+    /// which is none for an ignored variant, or each set field of a struct.
+    fn for_each_index(target: &Self::Target, f: impl FnMut(usize));
+}
+
+/// The error of `try_erase()` if the variant has less records than to erase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnderflowError {
+    /// The name of the variant.
+    pub name: &'static str,
+    /// The count of records of the variant.
+    pub count: usize,
+    /// The count of records to erase.
+    pub requested: usize,
+}
+
+impl fmt::Display for UnderflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot erase {} record(s) of `{}` which has {}",
+            self.requested, self.name, self.count
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnderflowError {}
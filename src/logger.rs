@@ -0,0 +1,74 @@
+//! A `log` adapter which counts the records by [`log::Level`](::log::Level).
+//!
+//! ```rust
+//! # use variant_counter::logger::{CountingLogger, Discard};
+//! use log::Log;
+//!
+//! let logger = CountingLogger::new(Discard);
+//! let counter = logger.counter();
+//! logger.log(
+//!     &log::Record::builder()
+//!         .level(log::Level::Warn)
+//!         .args(format_args!("disk is almost full"))
+//!         .build(),
+//! );
+//! assert_eq!(counter.lock().sum(), 1);
+//! ```
+
+use ::log::{Log, Metadata, Record};
+
+use crate::{builtin::LevelCounter, shared::SharedCounter};
+
+/// A [`Log`] which counts the level of each record, then forwards it to the inner logger.
+///
+/// Install it by `log::set_boxed_logger()` or `log::set_logger()`,
+/// use [`Discard`] as the inner logger to count only.
+#[derive(Debug)]
+pub struct CountingLogger<L> {
+    inner: L,
+    counter: SharedCounter<LevelCounter>,
+}
+
+impl<L: Log> CountingLogger<L> {
+    /// Count the records, then forward them to the `inner` logger.
+    pub fn new(inner: L) -> Self {
+        CountingLogger {
+            inner,
+            counter: SharedCounter::default(),
+        }
+    }
+
+    /// Get the handle to the live counter.
+    pub fn counter(&self) -> SharedCounter<LevelCounter> {
+        self.counter.clone()
+    }
+}
+
+impl<L: Log> Log for CountingLogger<L> {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        self.counter.lock().record(&record.level());
+        self.inner.log(record);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// A [`Log`] which discards every record.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Discard;
+
+impl Log for Discard {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn log(&self, _record: &Record<'_>) {}
+
+    fn flush(&self) {}
+}
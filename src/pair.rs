@@ -0,0 +1,362 @@
+//! Count the combinations of two types, such as `(Platform, Level)` pairs,
+//! or the transitions between consecutive values of a type.
+//!
+//! ```rust
+//! # use variant_counter::VariantCount;
+//! use variant_counter::pair::{PairCounter, TransitionCounter};
+//!
+//! #[derive(VariantCount)]
+//! enum Platform {
+//!     Android,
+//!     IOS,
+//! }
+//!
+//! #[derive(VariantCount)]
+//! enum Level {
+//!     Info,
+//!     Error,
+//! }
+//!
+//! let mut pairs = PairCounter::<Platform, Level>::new();
+//! pairs.record(&Platform::Android, &Level::Info);
+//! pairs.record(&Platform::Android, &Level::Error);
+//! pairs.record(&Platform::IOS, &Level::Error);
+//! assert_eq!(pairs.matrix(), &[[1, 1], [0, 1]]);
+//! assert_eq!(pairs.row_marginals(), [2, 1]);
+//! assert_eq!(pairs.column_marginals(), [1, 2]);
+//!
+//! let mut transitions = TransitionCounter::<Level>::new();
+//! transitions.record_all(&[Level::Info, Level::Info, Level::Error, Level::Info]);
+//! assert_eq!(transitions.pairs().matrix(), &[[1, 1], [1, 0]]);
+//! ```
+
+use core::fmt;
+
+use crate::Variants;
+
+/// The counter of the pairs of a variant of `A` and a variant of `B`, backed by an `[[usize; M]; N]` matrix
+/// where `N` and `M` are the counts of the variants of `A` and `B`.
+pub struct PairCounter<A: Variants, B: Variants> {
+    matrix: A::Array<B::Array<usize>>,
+}
+
+impl<A: Variants, B: Variants> PairCounter<A, B> {
+    /// Create an empty counter.
+    pub fn new() -> Self {
+        PairCounter {
+            matrix: A::array(B::array(0)),
+        }
+    }
+
+    /// Record a pair. It has no effect if either is an ignored variant,
+    /// a struct records the pair of each set field.
+    pub fn record(&mut self, a: &A::Target, b: &B::Target) {
+        let matrix = &mut self.matrix;
+        A::for_each_index(a, |row| {
+            let row = matrix.as_mut()[row].as_mut();
+            B::for_each_index(b, |column| {
+                row[column] = row[column].saturating_add(1);
+            });
+        });
+    }
+
+    /// Get the matrix, where the row is the variant of `A` and the column is the variant of `B`,
+    /// in the order of their `VARIANT_NAMES`.
+    #[inline]
+    pub fn matrix(&self) -> &A::Array<B::Array<usize>> {
+        &self.matrix
+    }
+
+    /// Get the count of a pair by the display names of the variants.
+    pub fn get(&self, a: &str, b: &str) -> Option<usize> {
+        let row = A::VARIANT_NAMES.iter().position(|name| *name == a)?;
+        let column = B::VARIANT_NAMES.iter().position(|name| *name == b)?;
+        Some(self.matrix.as_ref()[row].as_ref()[column])
+    }
+
+    /// Get the sum of all pairs.
+    pub fn sum(&self) -> usize {
+        self.rows().map(|row| row.iter().sum::<usize>()).sum()
+    }
+
+    /// Get the count of each variant of `A` in any pair.
+    pub fn row_marginals(&self) -> A::Array<usize> {
+        let mut marginals = A::array(0);
+        for (marginal, row) in marginals.as_mut().iter_mut().zip(self.rows()) {
+            *marginal = row.iter().sum();
+        }
+        marginals
+    }
+
+    /// Get the count of each variant of `B` in any pair.
+    pub fn column_marginals(&self) -> B::Array<usize> {
+        let mut marginals = B::array(0);
+        for row in self.rows() {
+            for (marginal, count) in marginals.as_mut().iter_mut().zip(row) {
+                *marginal += count;
+            }
+        }
+        marginals
+    }
+
+    /// Get the count of each group of `A` in any pair, in the order of its `GROUP_NAMES`.
+    #[cfg(feature = "std")]
+    pub fn row_group_marginals(&self) -> Vec<(&'static str, usize)> {
+        group_marginals::<A>(self.row_marginals().as_ref())
+    }
+
+    /// Get the count of each group of `B` in any pair, in the order of its `GROUP_NAMES`.
+    #[cfg(feature = "std")]
+    pub fn column_group_marginals(&self) -> Vec<(&'static str, usize)> {
+        group_marginals::<B>(self.column_marginals().as_ref())
+    }
+
+    /// Get the matrix of the groups, where the row is the group of `A` and the column is the group of `B`,
+    /// in the order of their `GROUP_NAMES`.
+    #[cfg(feature = "std")]
+    pub fn group_matrix(&self) -> Vec<Vec<usize>> {
+        let mut matrix = vec![vec![0; B::GROUP_NAMES.len()]; A::GROUP_NAMES.len()];
+        for (row, counts) in self.rows().enumerate() {
+            let group_row = &mut matrix[A::VARIANT_GROUPS[row]];
+            for (column, count) in counts.iter().enumerate() {
+                group_row[B::VARIANT_GROUPS[column]] += count;
+            }
+        }
+        matrix
+    }
+
+    /// Reset the records.
+    pub fn reset(&mut self) {
+        self.matrix = A::array(B::array(0));
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[usize]> {
+        self.matrix.as_ref().iter().map(|row| row.as_ref())
+    }
+}
+
+#[cfg(feature = "std")]
+fn group_marginals<T: Variants>(marginals: &[usize]) -> Vec<(&'static str, usize)> {
+    let mut groups = T::GROUP_NAMES
+        .iter()
+        .map(|name| (*name, 0))
+        .collect::<Vec<_>>();
+    for (index, count) in marginals.iter().enumerate() {
+        groups[T::VARIANT_GROUPS[index]].1 += count;
+    }
+    groups
+}
+
+impl<A: Variants, B: Variants> Default for PairCounter<A, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Variants, B: Variants> Clone for PairCounter<A, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A: Variants, B: Variants> Copy for PairCounter<A, B> {}
+
+impl<A: Variants, B: Variants> PartialEq for PairCounter<A, B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rows().eq(other.rows())
+    }
+}
+
+impl<A: Variants, B: Variants> Eq for PairCounter<A, B> {}
+
+impl<A: Variants, B: Variants> fmt::Debug for PairCounter<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PairCounter")
+            .field("matrix", &Rows(self))
+            .finish()
+    }
+}
+
+/// Format the rows of the matrix as a list without collecting them.
+struct Rows<'a, A: Variants, B: Variants>(&'a PairCounter<A, B>);
+
+impl<A: Variants, B: Variants> fmt::Debug for Rows<'_, A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.rows()).finish()
+    }
+}
+
+/// Format a contingency table, the rows are the variants of `A` and the columns are the variants of `B`,
+/// along with the marginals in the `total` row and column. The alternate form `{:#}` formats the groups instead.
+#[cfg(feature = "std")]
+impl<A: Variants, B: Variants> fmt::Display for PairCounter<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            let matrix = self.group_matrix();
+            write_contingency(f, A::GROUP_NAMES, B::GROUP_NAMES, |row, column| {
+                matrix[row][column]
+            })
+        } else {
+            write_contingency(f, A::VARIANT_NAMES, B::VARIANT_NAMES, |row, column| {
+                self.matrix.as_ref()[row].as_ref()[column]
+            })
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn write_contingency(
+    f: &mut fmt::Formatter<'_>,
+    rows: &[&str],
+    columns: &[&str],
+    count: impl Fn(usize, usize) -> usize,
+) -> fmt::Result {
+    const TOTAL: &str = "total";
+
+    let row_totals = (0..rows.len())
+        .map(|row| (0..columns.len()).map(|column| count(row, column)).sum())
+        .collect::<Vec<usize>>();
+    let column_totals = (0..columns.len())
+        .map(|column| (0..rows.len()).map(|row| count(row, column)).sum())
+        .collect::<Vec<usize>>();
+    let total = row_totals.iter().sum::<usize>();
+
+    let name_width = rows
+        .iter()
+        .map(|name| name.chars().count())
+        .chain(Some(TOTAL.len()))
+        .max()
+        .unwrap_or(0);
+    let widths = columns
+        .iter()
+        .zip(&column_totals)
+        .map(|(name, total)| name.chars().count().max(total.to_string().len()))
+        .collect::<Vec<_>>();
+    let total_width = TOTAL.len().max(total.to_string().len());
+
+    write!(f, "{:name_width$}", "", name_width = name_width)?;
+    for (name, width) in columns.iter().zip(&widths) {
+        write!(f, " {:>width$}", name, width = width)?;
+    }
+    writeln!(f, " {:>width$}", TOTAL, width = total_width)?;
+    for (row, name) in rows.iter().enumerate() {
+        write!(f, "{:name_width$}", name, name_width = name_width)?;
+        for (column, width) in widths.iter().enumerate() {
+            write!(f, " {:>width$}", count(row, column), width = width)?;
+        }
+        writeln!(f, " {:>width$}", row_totals[row], width = total_width)?;
+    }
+    write!(f, "{:name_width$}", TOTAL, name_width = name_width)?;
+    for (column_total, width) in column_totals.iter().zip(&widths) {
+        write!(f, " {:>width$}", column_total, width = width)?;
+    }
+    write!(f, " {:>width$}", total, width = total_width)
+}
+
+/// The counter of the transitions between consecutive values of `E`, such as the states of a state machine.
+///
+/// Ignored variants are skipped, so the transition goes from the last recorded value to the next one.
+pub struct TransitionCounter<E: Variants> {
+    pairs: PairCounter<E, E>,
+    previous: Option<E::Array<bool>>,
+}
+
+impl<E: Variants> TransitionCounter<E> {
+    /// Create an empty counter.
+    pub fn new() -> Self {
+        TransitionCounter {
+            pairs: PairCounter::new(),
+            previous: None,
+        }
+    }
+
+    /// Record the next value, which counts the transition from the previous value if any.
+    pub fn record(&mut self, target: &E::Target) {
+        let mut current = E::array(false);
+        let mut recorded = false;
+        E::for_each_index(target, |index| {
+            current.as_mut()[index] = true;
+            recorded = true;
+        });
+        if !recorded {
+            return;
+        }
+        if let Some(previous) = &self.previous {
+            let matrix = self.pairs.matrix.as_mut();
+            for (row, _) in previous
+                .as_ref()
+                .iter()
+                .enumerate()
+                .filter(|(_, set)| **set)
+            {
+                let row = matrix[row].as_mut();
+                for (column, _) in current.as_ref().iter().enumerate().filter(|(_, set)| **set) {
+                    row[column] = row[column].saturating_add(1);
+                }
+            }
+        }
+        self.previous = Some(current);
+    }
+
+    /// Record the values of a stream in order.
+    pub fn record_all<'a>(&mut self, targets: impl IntoIterator<Item = &'a E::Target>)
+    where
+        E::Target: 'a,
+    {
+        for target in targets {
+            self.record(target);
+        }
+    }
+
+    /// Forget the previous value, so the next value starts a new stream.
+    pub fn restart(&mut self) {
+        self.previous = None;
+    }
+
+    /// Get the counter of the transitions, where the row is the previous value and the column is the next one.
+    #[inline]
+    pub fn pairs(&self) -> &PairCounter<E, E> {
+        &self.pairs
+    }
+
+    /// Reset the records and forget the previous value.
+    pub fn reset(&mut self) {
+        self.pairs.reset();
+        self.previous = None;
+    }
+}
+
+impl<E: Variants> Default for TransitionCounter<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Variants> Clone for TransitionCounter<E> {
+    fn clone(&self) -> Self {
+        TransitionCounter {
+            pairs: self.pairs,
+            previous: self.previous,
+        }
+    }
+}
+
+impl<E: Variants> fmt::Debug for TransitionCounter<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransitionCounter")
+            .field("pairs", &self.pairs)
+            .field(
+                "previous",
+                &self.previous.as_ref().map(|previous| previous.as_ref()),
+            )
+            .finish()
+    }
+}
+
+/// Format the contingency table of the transitions, see the `Display` of [`PairCounter`].
+#[cfg(feature = "std")]
+impl<E: Variants> fmt::Display for TransitionCounter<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.pairs, f)
+    }
+}
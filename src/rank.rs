@@ -0,0 +1,41 @@
+//! Ranking helpers used by the derived `ranked()`, `top_k()` and `bottom_k()` methods.
+//!
+//! The entries are sorted in place in fixed arrays, so there is no allocation.
+//! Variants with the same frequency keep their declaration order.
+
+use core::cmp::Reverse;
+
+/// Sort the entries by frequency in descending order.
+pub fn descending<const N: usize>(
+    entries: [(&'static str, usize); N],
+) -> impl Iterator<Item = (&'static str, usize)> {
+    let mut indexed = indexed(entries);
+    indexed.sort_unstable_by_key(|(index, (_, freq))| (Reverse(*freq), *index));
+    IntoIterator::into_iter(indexed).map(|(_, entry)| entry)
+}
+
+/// Sort the entries by frequency in ascending order.
+pub fn ascending<const N: usize>(
+    entries: [(&'static str, usize); N],
+) -> impl Iterator<Item = (&'static str, usize)> {
+    let mut indexed = indexed(entries);
+    indexed.sort_unstable_by_key(|(index, (_, freq))| (*freq, *index));
+    IntoIterator::into_iter(indexed).map(|(_, entry)| entry)
+}
+
+/// Keep the entries which have been recorded, in declaration order.
+pub fn nonzero<const N: usize>(
+    entries: [(&'static str, usize); N],
+) -> impl Iterator<Item = (&'static str, usize)> {
+    IntoIterator::into_iter(entries).filter(|(_, freq)| *freq > 0)
+}
+
+fn indexed<const N: usize>(
+    entries: [(&'static str, usize); N],
+) -> [(usize, (&'static str, usize)); N] {
+    let mut indexed = [(0, ("", 0)); N];
+    for (index, entry) in IntoIterator::into_iter(entries).enumerate() {
+        indexed[index] = (index, entry);
+    }
+    indexed
+}
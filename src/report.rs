@@ -0,0 +1,274 @@
+//! Report the records periodically, such as shipping the records of each scrape interval to a metrics backend.
+//!
+//! A [`Reporter`] takes the records from a [`Take`] source once per interval and hands them to a [`Sink`],
+//! the time comes from a [`Clock`] which can be replaced by a [`ManualClock`] in tests.
+//!
+//! ```rust
+//! # use variant_counter::VariantCount;
+//! use std::time::Duration;
+//! use variant_counter::report::{ManualClock, Report, Reporter};
+//!
+//! #[derive(VariantCount)]
+//! enum Status {
+//!     Ok,
+//!     NotFound,
+//! }
+//!
+//! static COUNTER: StatusStaticCounter = StatusStaticCounter::new();
+//!
+//! let clock = ManualClock::new();
+//! let mut reports = Vec::new();
+//! let mut reporter = Reporter::new(
+//!     &COUNTER,
+//!     &clock,
+//!     |report: Report<StatusCounter>| reports.push(report.counter.sum()),
+//!     Duration::from_secs(10),
+//! );
+//!
+//! COUNTER.record(&Status::Ok);
+//! assert!(!reporter.tick());
+//! clock.advance(Duration::from_secs(10));
+//! assert!(reporter.tick());
+//! COUNTER.record(&Status::NotFound);
+//! COUNTER.record(&Status::NotFound);
+//! reporter.flush();
+//!
+//! drop(reporter);
+//! assert_eq!(reports, [1, 2]);
+//! ```
+
+use core::{cell::Cell, time::Duration};
+use std::{sync::Arc, time::Instant};
+
+/// A source of records which can be taken and reset in one step.
+///
+/// It is implemented by the derived `{Enum}StaticCounter` and `{Enum}ShardedCounter`,
+/// and by [`SharedCounter`](crate::shared::SharedCounter).
+pub trait Take {
+    /// The counter of the taken records.
+    type Counter;
+
+    /// Take the records and reset them.
+    fn take(&self) -> Self::Counter;
+}
+
+impl<T: Take + ?Sized> Take for &T {
+    type Counter = T::Counter;
+
+    #[inline]
+    fn take(&self) -> T::Counter {
+        (**self).take()
+    }
+}
+
+impl<T: Take + ?Sized> Take for Arc<T> {
+    type Counter = T::Counter;
+
+    #[inline]
+    fn take(&self) -> T::Counter {
+        (**self).take()
+    }
+}
+
+/// A monotonic clock.
+pub trait Clock {
+    /// The time elapsed since an arbitrary fixed point.
+    fn now(&self) -> Duration;
+
+    /// Block the current thread for the duration.
+    fn sleep(&self, duration: Duration);
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    #[inline]
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+
+    #[inline]
+    fn sleep(&self, duration: Duration) {
+        (**self).sleep(duration)
+    }
+}
+
+/// The real clock, which measures the time since it is created.
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    /// Start the clock.
+    pub fn new() -> Self {
+        SystemClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    #[inline]
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    #[inline]
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration)
+    }
+}
+
+/// A clock which only moves when it is told to, sleeping advances it immediately.
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now: Cell<Duration>,
+}
+
+impl ManualClock {
+    /// Create a clock at zero.
+    pub const fn new() -> Self {
+        ManualClock {
+            now: Cell::new(Duration::from_secs(0)),
+        }
+    }
+
+    /// Move the clock forward.
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+impl Clock for ManualClock {
+    #[inline]
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+
+    #[inline]
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration)
+    }
+}
+
+/// The records taken at the end of an interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Report<C> {
+    /// The taken records.
+    pub counter: C,
+    /// The time when the interval started, by the clock of the reporter.
+    pub start: Duration,
+    /// The time when the records were taken, by the clock of the reporter.
+    pub end: Duration,
+}
+
+/// The destination of the reports, such as a metrics backend.
+///
+/// It is implemented by closures which take a [`Report`].
+pub trait Sink<C> {
+    /// Receive the records of an interval.
+    fn report(&mut self, report: Report<C>);
+}
+
+impl<C, F: FnMut(Report<C>)> Sink<C> for F {
+    #[inline]
+    fn report(&mut self, report: Report<C>) {
+        self(report)
+    }
+}
+
+/// Take the records from the source once per interval, and hand them to the sink.
+///
+/// The intervals are aligned to the creation of the reporter, the missed ones are skipped
+/// and their records are included in the next report.
+#[derive(Debug)]
+pub struct Reporter<T, K, S> {
+    source: T,
+    clock: K,
+    sink: S,
+    interval: Duration,
+    start: Duration,
+    next: Duration,
+}
+
+impl<T: Take, K: Clock, S: Sink<T::Counter>> Reporter<T, K, S> {
+    /// Create a reporter, the first interval starts now.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interval is zero.
+    pub fn new(source: T, clock: K, sink: S, interval: Duration) -> Self {
+        assert!(
+            interval > Duration::from_secs(0),
+            "the interval of a reporter must not be zero"
+        );
+        let start = clock.now();
+        Reporter {
+            source,
+            clock,
+            sink,
+            interval,
+            start,
+            next: start + interval,
+        }
+    }
+
+    /// Report the records if the current interval has elapsed, returns whether it reported.
+    pub fn tick(&mut self) -> bool {
+        let now = self.clock.now();
+        if now < self.next {
+            return false;
+        }
+        while self.next <= now {
+            self.next += self.interval;
+        }
+        self.report(now);
+        true
+    }
+
+    /// Report the records now regardless of the interval, such as on shutdown.
+    pub fn flush(&mut self) {
+        let now = self.clock.now();
+        self.report(now);
+    }
+
+    /// Sleep until each interval elapses and report, while `keep_running` returns `true`.
+    /// The remaining records are flushed when it stops.
+    pub fn run(&mut self, mut keep_running: impl FnMut() -> bool) {
+        while keep_running() {
+            let now = self.clock.now();
+            if now < self.next {
+                self.clock.sleep(self.next - now);
+            }
+            self.tick();
+        }
+        self.flush();
+    }
+
+    /// Get the source of the records.
+    #[inline]
+    pub fn source(&self) -> &T {
+        &self.source
+    }
+
+    /// Get the sink of the reports.
+    #[inline]
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    fn report(&mut self, now: Duration) {
+        let counter = self.source.take();
+        self.sink.report(Report {
+            counter,
+            start: self.start,
+            end: now,
+        });
+        self.start = now;
+    }
+}
@@ -0,0 +1,234 @@
+//! The sampling used by the derived `{Enum}SampledCounter`, which records only a fraction of the values
+//! on very hot paths and scales the counts back up.
+//!
+//! The sampler draws the number of values to skip until the next sampled one, so the random number generator
+//! is only called once per sampled value, and the other values cost a decrement.
+//! The generator is injectable and seeded, so the sampling is reproducible.
+
+/// A source of random numbers for the sampling.
+///
+/// It is implemented by [`SplitMix64`] and by closures returning a `u64`.
+pub trait Rng {
+    /// The next uniformly distributed random number.
+    fn next_u64(&mut self) -> u64;
+}
+
+impl<F: FnMut() -> u64> Rng for F {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self()
+    }
+}
+
+/// The SplitMix64 generator, which is small, fast and deterministic for a seed. It is not cryptographically secure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Create a generator from the seed.
+    pub const fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+}
+
+impl Rng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// How the values are sampled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sampling {
+    /// Sample one of every `n` values, starting at a random offset.
+    OneIn(usize),
+    /// Sample each value with the probability independently.
+    Probability(f64),
+}
+
+impl Sampling {
+    /// The expected fraction of the sampled values.
+    pub fn rate(&self) -> f64 {
+        match *self {
+            Sampling::OneIn(n) => 1.0 / n as f64,
+            Sampling::Probability(p) => p,
+        }
+    }
+}
+
+/// Decide which values are sampled.
+#[derive(Debug, Clone)]
+pub struct Sampler<R> {
+    sampling: Sampling,
+    rng: R,
+    skip: usize,
+}
+
+impl<R: Rng> Sampler<R> {
+    /// Create a sampler.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` of `Sampling::OneIn(n)` is zero, or the probability is not in `(0, 1]`.
+    pub fn new(sampling: Sampling, mut rng: R) -> Self {
+        let skip = match sampling {
+            Sampling::OneIn(n) => {
+                assert!(n > 0, "cannot sample one in zero values");
+                (rng.next_u64() % n as u64) as usize
+            }
+            Sampling::Probability(p) => {
+                assert!(
+                    p > 0.0 && p <= 1.0,
+                    "the sampling probability must be in (0, 1], but it is {}",
+                    p
+                );
+                geometric(&mut rng, p)
+            }
+        };
+        Sampler {
+            sampling,
+            rng,
+            skip,
+        }
+    }
+
+    /// Get the sampling.
+    #[inline]
+    pub fn sampling(&self) -> Sampling {
+        self.sampling
+    }
+
+    /// Whether the next value is sampled.
+    #[inline]
+    pub fn sample(&mut self) -> bool {
+        if self.skip > 0 {
+            self.skip -= 1;
+            return false;
+        }
+        self.skip = match self.sampling {
+            Sampling::OneIn(n) => n - 1,
+            Sampling::Probability(p) => geometric(&mut self.rng, p),
+        };
+        true
+    }
+}
+
+/// The count of failures before the first success of Bernoulli trials with the probability `p`.
+fn geometric<R: Rng>(rng: &mut R, p: f64) -> usize {
+    if p >= 1.0 {
+        return 0;
+    }
+    // A uniform number in (0, 1].
+    let uniform = 1.0 - (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+    let skip = (uniform.ln() / (1.0 - p).ln()).floor();
+    if skip >= usize::MAX as f64 {
+        usize::MAX
+    } else {
+        skip as usize
+    }
+}
+
+/// The estimated count of a variant, along with its confidence interval.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    /// The display name of the variant.
+    pub name: &'static str,
+    /// The count of the sampled values.
+    pub sampled: usize,
+    /// The estimated count of all values, which is the sampled count scaled by the sampling rate.
+    pub estimate: f64,
+    /// The lower bound of the confidence interval, which is at least the sampled count.
+    pub low: f64,
+    /// The upper bound of the confidence interval.
+    pub high: f64,
+}
+
+impl Estimate {
+    /// Whether the interval contains the count.
+    pub fn contains(&self, count: usize) -> bool {
+        self.low <= count as f64 && count as f64 <= self.high
+    }
+}
+
+/// Estimate the count from the sampled count, the interval is the normal approximation
+/// of the binomial sampling at the `confidence` level, such as `0.95`.
+///
+/// It treats `Sampling::OneIn` like the probability `1/n`, which holds unless the values repeat
+/// in a period of `n`. The approximation is poor for small sampled counts.
+///
+/// # Panics
+///
+/// Panics if the confidence is not in `(0, 1)`.
+pub fn estimate(name: &'static str, sampled: usize, rate: f64, confidence: f64) -> Estimate {
+    assert!(
+        confidence > 0.0 && confidence < 1.0,
+        "the confidence must be in (0, 1), but it is {}",
+        confidence
+    );
+    let z = normal_quantile((1.0 + confidence) / 2.0);
+    let estimate = sampled as f64 / rate;
+    let error = z * (sampled as f64 * (1.0 - rate)).sqrt() / rate;
+    Estimate {
+        name,
+        sampled,
+        estimate,
+        low: (estimate - error).max(sampled as f64),
+        high: estimate + error,
+    }
+}
+
+/// The quantile function of the standard normal distribution, by Acklam's rational approximation
+/// whose relative error is below `1.15e-9`.
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const LOW: f64 = 0.024_25;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
@@ -0,0 +1,76 @@
+//! A counter shared between threads, such as a logger and the code reading the statistics.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A cloneable handle to a counter behind a mutex.
+///
+/// ```rust
+/// # use variant_counter::{shared::SharedCounter, VariantCount};
+/// #[derive(VariantCount)]
+/// enum Event {
+///     Login,
+///     Logout,
+/// }
+///
+/// let shared = SharedCounter::new(Event::counter());
+/// let handle = shared.clone();
+/// std::thread::spawn(move || handle.lock().record(&Event::Login))
+///     .join()
+///     .unwrap();
+/// assert_eq!(shared.lock().sum(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct SharedCounter<C> {
+    inner: Arc<Mutex<C>>,
+}
+
+impl<C> SharedCounter<C> {
+    /// Share the counter.
+    pub fn new(counter: C) -> Self {
+        SharedCounter {
+            inner: Arc::new(Mutex::new(counter)),
+        }
+    }
+
+    /// Lock the counter to record or read it.
+    ///
+    /// A counter is always valid, so it is still available if another thread panicked with the lock.
+    pub fn lock(&self) -> MutexGuard<'_, C> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Get a copy of the counter at this moment.
+    pub fn snapshot(&self) -> C
+    where
+        C: Clone,
+    {
+        self.lock().clone()
+    }
+
+    /// Take the counter and reset it under the lock, so no record is lost or counted twice.
+    pub fn take(&self) -> C
+    where
+        C: Default,
+    {
+        core::mem::take(&mut *self.lock())
+    }
+}
+
+impl<C> Clone for SharedCounter<C> {
+    fn clone(&self) -> Self {
+        SharedCounter {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<C: Default> crate::report::Take for SharedCounter<C> {
+    type Counter = C;
+
+    #[inline]
+    fn take(&self) -> C {
+        SharedCounter::take(self)
+    }
+}
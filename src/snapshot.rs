@@ -0,0 +1,370 @@
+//! The binary snapshot format used by the derived `to_bytes()` and `from_bytes()` methods.
+//!
+//! A snapshot starts with a header:
+//!
+//! | Bytes | Content |
+//! |-------|---------|
+//! | 2     | The magic `b"VC"` |
+//! | 1     | The format version, which is `1` |
+//! | 1     | The flags, `0x01` for zero-run compression, `0x02` for by-name mode |
+//! | 8     | The schema hash in little endian, see [`schema_hash()`] |
+//! | varint | The count of slots, or the count of names in by-name mode |
+//!
+//! Then, in positional mode, each count follows as an unsigned LEB128 varint in the order of the slots.
+//! With zero-run compression, a run of zeros is written as a `0` followed by the length of the run.
+//! Decoding a positional snapshot fails if the schema hash differs.
+//!
+//! In by-name mode, each slot is written as the length and UTF-8 bytes of its name followed by its count,
+//! slots of zero count are skipped with zero-run compression. Decoding maps the slots by name,
+//! so variants can be added, removed or reordered. A slot is mapped by its current name or a previous name
+//! declared by `#[counter(alias = "...")]`, the counts of other names are dropped and reported in [`Loaded`].
+//! Encode in by-name mode if the snapshots should survive the changes of variants.
+
+#[cfg(feature = "std")]
+use core::convert::TryFrom;
+use core::fmt;
+
+/// The magic bytes of a snapshot.
+pub const MAGIC: [u8; 2] = *b"VC";
+/// The current format version.
+pub const VERSION: u8 = 1;
+
+#[cfg(feature = "std")]
+const FLAG_ZERO_RUN: u8 = 0x01;
+#[cfg(feature = "std")]
+const FLAG_BY_NAME: u8 = 0x02;
+#[cfg(feature = "std")]
+const HEADER_LEN: usize = 12;
+
+/// The options to encode a snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Encoding {
+    /// Compress the runs of zero counts. Enabled by default.
+    pub zero_run: bool,
+    /// Write the name of each slot, so it can be decoded after the variants changed. Disabled by default.
+    pub by_name: bool,
+}
+
+impl Default for Encoding {
+    fn default() -> Self {
+        Encoding {
+            zero_run: true,
+            by_name: false,
+        }
+    }
+}
+
+/// The error of decoding a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SnapshotError {
+    /// The bytes end in the middle of the snapshot.
+    UnexpectedEnd,
+    /// The bytes don't start with the magic `b"VC"`.
+    InvalidMagic,
+    /// The format version is newer than this crate supports.
+    UnsupportedVersion(u8),
+    /// The variants have changed incompatibly since the snapshot was encoded in positional mode.
+    SchemaMismatch {
+        /// The schema hash of the counter.
+        expected: u64,
+        /// The schema hash in the snapshot.
+        found: u64,
+    },
+    /// A varint is malformed or doesn't fit in `usize`.
+    InvalidVarint,
+    /// A name in a by-name snapshot is not valid UTF-8.
+    InvalidName,
+    /// The counts don't match the count of slots or names in the header.
+    InvalidLength,
+    /// There are bytes after the snapshot.
+    TrailingBytes,
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::UnexpectedEnd => f.write_str("unexpected end of snapshot"),
+            SnapshotError::InvalidMagic => f.write_str("not a variant counter snapshot"),
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "unsupported snapshot version {}", version)
+            }
+            SnapshotError::SchemaMismatch { expected, found } => write!(
+                f,
+                "the variants have changed, expected schema {:016x} but found {:016x}",
+                expected, found
+            ),
+            SnapshotError::InvalidVarint => f.write_str("invalid varint in snapshot"),
+            SnapshotError::InvalidName => f.write_str("invalid variant name in snapshot"),
+            SnapshotError::InvalidLength => {
+                f.write_str("the counts don't match the snapshot length")
+            }
+            SnapshotError::TrailingBytes => f.write_str("trailing bytes after snapshot"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SnapshotError {}
+
+/// The layout of the slots of a counter, which maps the names in a snapshot to the slots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Schema<'a> {
+    /// The hash of the slot names in order, see [`schema_hash()`].
+    pub hash: u64,
+    /// The name of each slot.
+    pub names: &'a [&'a str],
+    /// The previous names of the slots declared by `#[counter(alias = "...")]`, along with the slot index.
+    pub aliases: &'a [(&'a str, usize)],
+    /// The names of the removed variants declared by `#[counter(retired = "...")]`.
+    pub retired: &'a [&'a str],
+}
+
+impl<'a> Schema<'a> {
+    /// The slot index of a current or previous name.
+    pub fn resolve(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| *n == name).or_else(|| {
+            self.aliases
+                .iter()
+                .find(|(alias, _)| *alias == name)
+                .map(|(_, index)| *index)
+        })
+    }
+
+    /// Whether the name belongs to a variant which has been removed on purpose.
+    pub fn is_retired(&self, name: &str) -> bool {
+        self.retired.contains(&name)
+    }
+
+    /// Add the count to the slot of the name, or to the dropped counts if the name maps to no slot.
+    #[cfg(feature = "std")]
+    pub(crate) fn load(
+        &self,
+        counts: &mut [usize],
+        dropped: &mut Vec<Dropped>,
+        name: &str,
+        count: usize,
+    ) {
+        match self.resolve(name) {
+            Some(index) => counts[index] = counts[index].saturating_add(count),
+            None if count > 0 => dropped.push(Dropped {
+                name: name.to_string(),
+                count,
+                retired: self.is_retired(name),
+            }),
+            None => {}
+        }
+    }
+}
+
+/// The count of a name in a snapshot which doesn't map to any slot.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Dropped {
+    /// The name in the snapshot.
+    pub name: String,
+    /// The dropped count.
+    pub count: usize,
+    /// Whether the name is declared by `#[counter(retired = "...")]`, otherwise it's unknown to the counter.
+    pub retired: bool,
+}
+
+/// A counter loaded from a snapshot, along with the counts which can't be mapped to the counter.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loaded<C> {
+    /// The loaded counter.
+    pub counter: C,
+    /// The dropped counts in the order of the snapshot, names of zero count are not reported.
+    pub dropped: Vec<Dropped>,
+}
+
+#[cfg(feature = "std")]
+impl<C> Loaded<C> {
+    /// The dropped counts whose names are not declared by `#[counter(retired = "...")]`,
+    /// which usually means a variant was renamed without an alias.
+    pub fn unknown(&self) -> impl Iterator<Item = &Dropped> {
+        self.dropped.iter().filter(|dropped| !dropped.retired)
+    }
+}
+
+/// The FNV-1a hash of the slot names in order, each name is terminated by a `0xff` byte
+/// which never appears in UTF-8.
+pub const fn schema_hash(names: &[&str]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = OFFSET;
+    let mut i = 0;
+    while i < names.len() {
+        let bytes = names[i].as_bytes();
+        let mut j = 0;
+        while j < bytes.len() {
+            hash = (hash ^ bytes[j] as u64).wrapping_mul(PRIME);
+            j += 1;
+        }
+        hash = (hash ^ 0xff).wrapping_mul(PRIME);
+        i += 1;
+    }
+    hash
+}
+
+/// Encode the counts of the slots.
+#[cfg(feature = "std")]
+pub fn encode(schema: &Schema<'_>, counts: &[usize], encoding: Encoding) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN + counts.len() + 1);
+    bytes.extend_from_slice(&MAGIC);
+    bytes.push(VERSION);
+    let mut flags = 0;
+    if encoding.zero_run {
+        flags |= FLAG_ZERO_RUN;
+    }
+    if encoding.by_name {
+        flags |= FLAG_BY_NAME;
+    }
+    bytes.push(flags);
+    bytes.extend_from_slice(&schema.hash.to_le_bytes());
+    if encoding.by_name {
+        let entries = schema
+            .names
+            .iter()
+            .zip(counts)
+            .filter(|(_, count)| !encoding.zero_run || **count > 0);
+        write_varint(&mut bytes, entries.clone().count() as u64);
+        for (name, count) in entries {
+            write_varint(&mut bytes, name.len() as u64);
+            bytes.extend_from_slice(name.as_bytes());
+            write_varint(&mut bytes, *count as u64);
+        }
+    } else {
+        write_varint(&mut bytes, counts.len() as u64);
+        let mut index = 0;
+        while index < counts.len() {
+            let count = counts[index];
+            if encoding.zero_run && count == 0 {
+                let run = counts[index..]
+                    .iter()
+                    .take_while(|count| **count == 0)
+                    .count();
+                write_varint(&mut bytes, 0);
+                write_varint(&mut bytes, run as u64);
+                index += run;
+            } else {
+                write_varint(&mut bytes, count as u64);
+                index += 1;
+            }
+        }
+    }
+    bytes
+}
+
+/// Decode the counts of the slots, `counts` must be zeroed.
+/// Returns the counts of the names which don't map to any slot.
+#[cfg(feature = "std")]
+pub fn decode(
+    bytes: &[u8],
+    schema: &Schema<'_>,
+    counts: &mut [usize],
+) -> Result<Vec<Dropped>, SnapshotError> {
+    let mut reader = Reader { bytes };
+    if reader.take(2)? != MAGIC {
+        return Err(SnapshotError::InvalidMagic);
+    }
+    let version = reader.take(1)?[0];
+    if version != VERSION {
+        return Err(SnapshotError::UnsupportedVersion(version));
+    }
+    let flags = reader.take(1)?[0];
+    let mut hash = [0; 8];
+    hash.copy_from_slice(reader.take(8)?);
+    let found = u64::from_le_bytes(hash);
+    let len = reader.varint()?;
+
+    if flags & FLAG_BY_NAME != 0 {
+        let mut dropped = Vec::new();
+        for _ in 0..len {
+            let name_len = reader.varint()?;
+            let name = core::str::from_utf8(reader.take(name_len)?)
+                .map_err(|_| SnapshotError::InvalidName)?;
+            let count = reader.varint()?;
+            schema.load(counts, &mut dropped, name, count);
+        }
+        if !reader.bytes.is_empty() {
+            return Err(SnapshotError::TrailingBytes);
+        }
+        return Ok(dropped);
+    }
+
+    if found != schema.hash {
+        return Err(SnapshotError::SchemaMismatch {
+            expected: schema.hash,
+            found,
+        });
+    }
+    if len != counts.len() {
+        return Err(SnapshotError::InvalidLength);
+    }
+    let mut index = 0;
+    while index < len {
+        let count = reader.varint()?;
+        if flags & FLAG_ZERO_RUN != 0 && count == 0 {
+            let run = reader.varint()?;
+            if run == 0 || run > len - index {
+                return Err(SnapshotError::InvalidLength);
+            }
+            index += run;
+        } else {
+            counts[index] = count;
+            index += 1;
+        }
+    }
+    if !reader.bytes.is_empty() {
+        return Err(SnapshotError::TrailingBytes);
+    }
+    Ok(Vec::new())
+}
+
+#[cfg(feature = "std")]
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+#[cfg(feature = "std")]
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+#[cfg(feature = "std")]
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+        if self.bytes.len() < len {
+            return Err(SnapshotError::UnexpectedEnd);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn varint(&mut self) -> Result<usize, SnapshotError> {
+        let mut value = 0u64;
+        let mut shift = 0;
+        loop {
+            let byte = self.take(1)?[0];
+            if shift == 63 && byte > 1 {
+                return Err(SnapshotError::InvalidVarint);
+            }
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+            if shift > 63 {
+                return Err(SnapshotError::InvalidVarint);
+            }
+        }
+        usize::try_from(value).map_err(|_| SnapshotError::InvalidVarint)
+    }
+}
@@ -0,0 +1,272 @@
+//! Information-theoretic statistics over variant frequencies.
+//!
+//! The derived counters call into these functions, you can also use them
+//! directly on any frequency slice. All logarithms are base 2, so the results
+//! are measured in bits.
+
+/// Iterate the probability of each frequency, the `total` must not be zero.
+fn probabilities(frequency: &[usize], total: usize) -> impl Iterator<Item = f64> + '_ {
+    frequency
+        .iter()
+        .map(move |freq| *freq as f64 / total as f64)
+}
+
+fn total(frequency: &[usize]) -> usize {
+    frequency.iter().sum()
+}
+
+/// Get the Shannon entropy of the frequency.
+///
+/// Returns `0.0` if the total frequency is zero.
+pub fn entropy(frequency: &[usize]) -> f64 {
+    let total = total(frequency);
+    if total == 0 {
+        return 0.0;
+    }
+
+    -probabilities(frequency, total)
+        .filter(|p| *p > 0.0)
+        .map(|p| p * p.log2())
+        .sum::<f64>()
+}
+
+/// Get the Shannon entropy divided by the maximum entropy `log2(n)`,
+/// which is always in the range `[0.0, 1.0]`.
+///
+/// Returns `0.0` if the total frequency is zero or there is only one variant.
+pub fn normalized_entropy(frequency: &[usize]) -> f64 {
+    if frequency.len() <= 1 {
+        return 0.0;
+    }
+
+    entropy(frequency) / (frequency.len() as f64).log2()
+}
+
+/// Get the Gini impurity of the frequency, which is `1 - Σp²`.
+///
+/// Returns `0.0` if the total frequency is zero.
+pub fn gini_impurity(frequency: &[usize]) -> f64 {
+    let total = total(frequency);
+    if total == 0 {
+        return 0.0;
+    }
+
+    1.0 - probabilities(frequency, total).map(|p| p * p).sum::<f64>()
+}
+
+/// Get the Kullback-Leibler divergence `D(p || q)`.
+///
+/// Returns `0.0` if both are empty, and `f64::INFINITY` if only one of them is empty
+/// or `q` has no record of a variant which `p` has.
+///
+/// # Panics
+///
+/// Panics if `p` and `q` have different lengths.
+pub fn kl_divergence(p: &[usize], q: &[usize]) -> f64 {
+    assert_eq!(p.len(), q.len(), "frequency length mismatch");
+
+    let (p_total, q_total) = (total(p), total(q));
+    match (p_total, q_total) {
+        (0, 0) => return 0.0,
+        (0, _) | (_, 0) => return f64::INFINITY,
+        _ => {}
+    }
+
+    probabilities(p, p_total)
+        .zip(probabilities(q, q_total))
+        .filter(|(p, _)| *p > 0.0)
+        .map(|(p, q)| {
+            if q == 0.0 {
+                f64::INFINITY
+            } else {
+                p * (p / q).log2()
+            }
+        })
+        .sum()
+}
+
+/// Get the Jensen-Shannon divergence of `p` and `q`,
+/// which is symmetric and always in the range `[0.0, 1.0]`.
+///
+/// Returns `0.0` if both are empty, and `1.0` if only one of them is empty.
+///
+/// # Panics
+///
+/// Panics if `p` and `q` have different lengths.
+pub fn js_divergence(p: &[usize], q: &[usize]) -> f64 {
+    assert_eq!(p.len(), q.len(), "frequency length mismatch");
+
+    let (p_total, q_total) = (total(p), total(q));
+    match (p_total, q_total) {
+        (0, 0) => return 0.0,
+        (0, _) | (_, 0) => return 1.0,
+        _ => {}
+    }
+
+    probabilities(p, p_total)
+        .zip(probabilities(q, q_total))
+        .map(|(p, q)| {
+            let m = (p + q) / 2.0;
+            let kl = |x: f64| if x > 0.0 { x * (x / m).log2() } else { 0.0 };
+            (kl(p) + kl(q)) / 2.0
+        })
+        .sum()
+}
+
+/// The result of Pearson's chi-square goodness-of-fit test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquare<const N: usize> {
+    /// The chi-square statistic.
+    pub statistic: f64,
+    /// The degrees of freedom, which is the number of variants minus one.
+    pub degrees_of_freedom: usize,
+    /// The probability to observe a statistic at least as extreme as this one,
+    /// if the frequency follows the expected distribution.
+    pub p_value: f64,
+    /// The Pearson residual `(observed - expected) / sqrt(expected)` of each variant,
+    /// sorted by the absolute value in descending order.
+    residuals: [(&'static str, f64); N],
+}
+
+impl<const N: usize> ChiSquare<N> {
+    /// Get at most `k` variants which deviate most from the expected distribution,
+    /// along with their Pearson residuals.
+    pub fn most_deviating(&self, k: usize) -> &[(&'static str, f64)] {
+        &self.residuals[..k.min(N)]
+    }
+
+    /// Get the Pearson residual of each variant, sorted by the absolute value in descending order.
+    pub fn residuals(&self) -> &[(&'static str, f64); N] {
+        &self.residuals
+    }
+
+    /// Whether the frequency deviates from the expected distribution
+    /// at the significance level `alpha`, such as `0.05`.
+    pub fn is_significant(&self, alpha: f64) -> bool {
+        self.p_value < alpha
+    }
+}
+
+/// Perform Pearson's chi-square goodness-of-fit test.
+///
+/// The `expected` proportions are normalized by their sum, so they don't need to sum up to one.
+/// A variant expected to never occur makes the statistic infinite once it has been recorded.
+pub fn chi_square<const N: usize>(
+    names: [&'static str; N],
+    frequency: &[usize; N],
+    expected: &[f64; N],
+) -> ChiSquare<N> {
+    let total = total(frequency) as f64;
+    let expected_total = expected.iter().sum::<f64>();
+
+    let mut statistic = 0.0;
+    let mut residuals = [("", 0.0); N];
+    for (index, name) in names.iter().enumerate() {
+        let observed = frequency[index] as f64;
+        let expected = if expected_total > 0.0 {
+            total * expected[index] / expected_total
+        } else {
+            0.0
+        };
+        let residual = if expected > 0.0 {
+            (observed - expected) / expected.sqrt()
+        } else if observed > 0.0 {
+            f64::INFINITY
+        } else {
+            0.0
+        };
+        statistic += residual * residual;
+        residuals[index] = (*name, residual);
+    }
+    residuals.sort_unstable_by(|(_, a), (_, b)| b.abs().total_cmp(&a.abs()));
+
+    let degrees_of_freedom = N.saturating_sub(1);
+    let p_value = if degrees_of_freedom == 0 || total == 0.0 {
+        1.0
+    } else {
+        upper_regularized_gamma(degrees_of_freedom as f64 / 2.0, statistic / 2.0)
+    };
+
+    ChiSquare {
+        statistic,
+        degrees_of_freedom,
+        p_value,
+        residuals,
+    }
+}
+
+/// The natural logarithm of the gamma function, using the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.120_865_097_386_617_9e-2,
+        -0.539_523_938_495_3e-5,
+    ];
+
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let series = COEFFICIENTS
+        .iter()
+        .enumerate()
+        .fold(1.000_000_000_190_015, |acc, (i, c)| {
+            acc + c / (x + 1.0 + i as f64)
+        });
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
+
+/// The upper regularized incomplete gamma function `Q(a, x)`.
+fn upper_regularized_gamma(a: f64, x: f64) -> f64 {
+    const EPSILON: f64 = 1e-15;
+    const MAX_ITERATIONS: usize = 1000;
+
+    if x.is_infinite() {
+        return 0.0;
+    }
+    if x <= 0.0 {
+        return 1.0;
+    }
+
+    let ln_prefix = a * x.ln() - x - ln_gamma(a);
+    if x < a + 1.0 {
+        // Series representation of the lower function P(a, x).
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..MAX_ITERATIONS {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        (1.0 - sum * ln_prefix.exp()).max(0.0)
+    } else {
+        // Continued fraction representation of Q(a, x), by the modified Lentz's method.
+        let tiny = f64::MIN_POSITIVE / EPSILON;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for n in 1..MAX_ITERATIONS {
+            let an = -(n as f64) * (n as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+        ln_prefix.exp() * h
+    }
+}
@@ -0,0 +1,115 @@
+//! The count used by the derived `{Enum}StaticCounter`.
+//!
+//! It is an atomic on targets with atomics, otherwise it is protected by a critical section,
+//! which requires an implementation of the `critical-section` crate for the target.
+
+use core::fmt;
+
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// A count which can be updated by a shared reference.
+pub struct Count {
+    #[cfg(target_has_atomic = "ptr")]
+    value: AtomicUsize,
+    #[cfg(not(target_has_atomic = "ptr"))]
+    value: critical_section::Mutex<core::cell::Cell<usize>>,
+}
+
+impl Count {
+    /// Create a count of zero.
+    pub const fn new() -> Self {
+        Count {
+            #[cfg(target_has_atomic = "ptr")]
+            value: AtomicUsize::new(0),
+            #[cfg(not(target_has_atomic = "ptr"))]
+            value: critical_section::Mutex::new(core::cell::Cell::new(0)),
+        }
+    }
+
+    /// Add `n` to the count, it saturates at `usize::MAX`.
+    #[inline]
+    pub fn add(&self, n: usize) {
+        #[cfg(target_has_atomic = "ptr")]
+        let _ = self
+            .value
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |value| {
+                Some(value.saturating_add(n))
+            });
+        #[cfg(not(target_has_atomic = "ptr"))]
+        critical_section::with(|cs| {
+            let value = self.value.borrow(cs);
+            value.set(value.get().saturating_add(n));
+        });
+    }
+
+    /// Get the count.
+    #[inline]
+    pub fn load(&self) -> usize {
+        #[cfg(target_has_atomic = "ptr")]
+        return self.value.load(Ordering::Relaxed);
+        #[cfg(not(target_has_atomic = "ptr"))]
+        return critical_section::with(|cs| self.value.borrow(cs).get());
+    }
+
+    /// Replace the count with `value`, returns the previous count.
+    #[inline]
+    pub fn swap(&self, value: usize) -> usize {
+        #[cfg(target_has_atomic = "ptr")]
+        return self.value.swap(value, Ordering::Relaxed);
+        #[cfg(not(target_has_atomic = "ptr"))]
+        return critical_section::with(|cs| self.value.borrow(cs).replace(value));
+    }
+
+    /// Reset the count to zero.
+    #[inline]
+    pub fn reset(&self) {
+        self.swap(0);
+    }
+}
+
+impl Default for Count {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Count {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.load(), f)
+    }
+}
+
+/// Pads and aligns the value to its own cache line, to avoid false sharing between threads.
+#[derive(Debug, Default)]
+#[repr(align(128))]
+pub struct CachePadded<T>(T);
+
+impl<T> CachePadded<T> {
+    /// Pad the value.
+    pub const fn new(value: T) -> Self {
+        CachePadded(value)
+    }
+}
+
+impl<T> core::ops::Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Get the shard of the current thread in the `shards`.
+///
+/// Each thread gets the next shard on its first call, so threads are spread evenly over the shards.
+#[cfg(feature = "std")]
+pub fn shard_index(shards: usize) -> usize {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    std::thread_local! {
+        static INDEX: usize = NEXT.fetch_add(1, Ordering::Relaxed);
+    }
+    INDEX.with(|index| index % shards)
+}
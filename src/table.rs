@@ -0,0 +1,308 @@
+//! The CSV and TSV tables used by the derived `write_csv()` and `read_csv()` methods.
+//!
+//! A counter is written as rows of `variant,group,count,weighted,percent` under a header row.
+//! Several counters are written as a table with one column per variant and one row per counter,
+//! the header row holds the variant names.
+//!
+//! Both are read back by the header, the columns are mapped by the current names and the aliases declared by
+//! `#[counter(alias = "...")]`, the counts of other names are dropped and reported in [`Loaded`](crate::snapshot::Loaded).
+//! A field is quoted if it contains the delimiter, a quote or a line break, and a quote is escaped by doubling it.
+
+use core::fmt;
+use std::io;
+
+use crate::snapshot::{Dropped, Schema};
+
+/// The format of a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Format {
+    /// Comma-separated values, the default.
+    #[default]
+    Csv,
+    /// Tab-separated values.
+    Tsv,
+}
+
+impl Format {
+    /// The delimiter of the fields.
+    pub const fn delimiter(self) -> char {
+        match self {
+            Format::Csv => ',',
+            Format::Tsv => '\t',
+        }
+    }
+}
+
+/// The header of the rows written by [`write_rows()`].
+pub const ROW_HEADER: [&str; 5] = ["variant", "group", "count", "weighted", "percent"];
+
+/// A row of a counter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Row<'a> {
+    /// The display name of the variant.
+    pub variant: &'a str,
+    /// The name of the group of the variant.
+    pub group: &'a str,
+    /// The count of the variant.
+    pub count: usize,
+    /// The count multiplied by the weight of the variant.
+    pub weighted: usize,
+    /// The percent of the count in the total.
+    pub percent: f64,
+}
+
+/// The error of reading a table.
+#[derive(Debug)]
+pub enum TableError {
+    /// The reader failed.
+    Io(io::Error),
+    /// A quoted field is not closed until the end.
+    UnterminatedQuote {
+        /// The line where the row starts.
+        line: usize,
+    },
+    /// The header has no column of the name.
+    MissingColumn(&'static str),
+    /// A row has a different count of fields from the header.
+    ColumnMismatch {
+        /// The line where the row starts.
+        line: usize,
+    },
+    /// A count is not an unsigned integer.
+    InvalidCount {
+        /// The line where the row starts.
+        line: usize,
+        /// The invalid field.
+        value: String,
+    },
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableError::Io(error) => write!(f, "failed to read table: {}", error),
+            TableError::UnterminatedQuote { line } => {
+                write!(f, "unterminated quote in the row at line {}", line)
+            }
+            TableError::MissingColumn(name) => write!(f, "missing column `{}`", name),
+            TableError::ColumnMismatch { line } => write!(
+                f,
+                "the row at line {} doesn't match the columns of the header",
+                line
+            ),
+            TableError::InvalidCount { line, value } => {
+                write!(f, "invalid count `{}` at line {}", value, line)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TableError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TableError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for TableError {
+    fn from(error: io::Error) -> Self {
+        TableError::Io(error)
+    }
+}
+
+/// Write the rows of a counter under the [`ROW_HEADER`].
+pub fn write_rows<'a, W: io::Write>(
+    mut writer: W,
+    format: Format,
+    rows: impl IntoIterator<Item = Row<'a>>,
+) -> io::Result<()> {
+    write_record(&mut writer, format, ROW_HEADER.iter().copied())?;
+    for row in rows {
+        let count = row.count.to_string();
+        let weighted = row.weighted.to_string();
+        let percent = format!("{:.2}", row.percent);
+        write_record(
+            &mut writer,
+            format,
+            IntoIterator::into_iter([row.variant, row.group, &count, &weighted, &percent]),
+        )?;
+    }
+    Ok(())
+}
+
+/// Write the counts of several counters, one row per counter under the header of `names`.
+pub fn write_table<'a, W: io::Write>(
+    mut writer: W,
+    format: Format,
+    names: &[&str],
+    rows: impl IntoIterator<Item = &'a [usize]>,
+) -> io::Result<()> {
+    write_record(&mut writer, format, names.iter().copied())?;
+    for counts in rows {
+        let fields = counts
+            .iter()
+            .map(|count| count.to_string())
+            .collect::<Vec<_>>();
+        write_record(&mut writer, format, fields.iter().map(|field| &field[..]))?;
+    }
+    Ok(())
+}
+
+/// Read the rows written by [`write_rows()`] into the slots, `counts` must be zeroed.
+/// Only the `variant` and `count` columns are read.
+/// Returns the counts of the names which don't map to any slot.
+pub fn read_rows<R: io::Read>(
+    reader: R,
+    format: Format,
+    schema: &Schema<'_>,
+    counts: &mut [usize],
+) -> Result<Vec<Dropped>, TableError> {
+    let records = read_records(reader, format)?;
+    let mut records = records.into_iter();
+    let header = match records.next() {
+        Some((_, header)) => header,
+        None => return Err(TableError::MissingColumn("variant")),
+    };
+    let column = |name: &'static str| {
+        header
+            .iter()
+            .position(|field| field == name)
+            .ok_or(TableError::MissingColumn(name))
+    };
+    let variant_column = column("variant")?;
+    let count_column = column("count")?;
+
+    let mut dropped = Vec::new();
+    for (line, record) in records {
+        if record.len() != header.len() {
+            return Err(TableError::ColumnMismatch { line });
+        }
+        let count = parse_count(line, &record[count_column])?;
+        schema.load(counts, &mut dropped, &record[variant_column], count);
+    }
+    Ok(dropped)
+}
+
+/// Read the table written by [`write_table()`], `f` is called with the zeroed slots
+/// and the dropped counts of each row.
+pub fn read_table<R: io::Read>(
+    reader: R,
+    format: Format,
+    schema: &Schema<'_>,
+    mut f: impl FnMut(&[usize], Vec<Dropped>),
+) -> Result<(), TableError> {
+    let records = read_records(reader, format)?;
+    let mut records = records.into_iter();
+    let header = match records.next() {
+        Some((_, header)) => header,
+        None => return Ok(()),
+    };
+
+    let mut counts = vec![0; schema.names.len()];
+    for (line, record) in records {
+        if record.len() != header.len() {
+            return Err(TableError::ColumnMismatch { line });
+        }
+        counts.iter_mut().for_each(|count| *count = 0);
+        let mut dropped = Vec::new();
+        for (name, field) in header.iter().zip(&record) {
+            let count = parse_count(line, field)?;
+            schema.load(&mut counts, &mut dropped, name, count);
+        }
+        f(&counts, dropped);
+    }
+    Ok(())
+}
+
+fn parse_count(line: usize, field: &str) -> Result<usize, TableError> {
+    field.trim().parse().map_err(|_| TableError::InvalidCount {
+        line,
+        value: field.to_string(),
+    })
+}
+
+fn write_record<'a, W: io::Write>(
+    writer: &mut W,
+    format: Format,
+    fields: impl Iterator<Item = &'a str>,
+) -> io::Result<()> {
+    let delimiter = format.delimiter();
+    let mut record = String::new();
+    for (index, field) in fields.enumerate() {
+        if index > 0 {
+            record.push(delimiter);
+        }
+        if field.contains([delimiter, '"', '\n', '\r']) {
+            record.push('"');
+            record.push_str(&field.replace('"', "\"\""));
+            record.push('"');
+        } else {
+            record.push_str(field);
+        }
+    }
+    record.push('\n');
+    writer.write_all(record.as_bytes())
+}
+
+/// Split the text into records of fields, along with the line where each record starts.
+/// Blank lines are skipped.
+fn read_records<R: io::Read>(
+    mut reader: R,
+    format: Format,
+) -> Result<Vec<(usize, Vec<String>)>, TableError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let delimiter = format.delimiter();
+
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut start = 1;
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => quoted = true,
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(core::mem::take(&mut field));
+                if record.len() > 1 || !record[0].is_empty() {
+                    records.push((start, core::mem::take(&mut record)));
+                } else {
+                    record.clear();
+                }
+                line += 1;
+                start = line;
+            }
+            c if c == delimiter => record.push(core::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(TableError::UnterminatedQuote { line: start });
+    }
+    if !record.is_empty() || !field.is_empty() {
+        record.push(field);
+        records.push((start, record));
+    }
+    Ok(records)
+}
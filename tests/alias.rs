@@ -0,0 +1,116 @@
+#![cfg(feature = "std")]
+#![allow(unused)]
+use variant_counter::{
+    snapshot::{Dropped, Encoding},
+    *,
+};
+
+mod v1 {
+    use variant_counter::*;
+
+    #[derive(VariantCount)]
+    pub enum Status {
+        Ok,
+        Missing,
+        Timeout,
+        Teapot,
+        #[counter(ignore, track)]
+        Retry,
+    }
+}
+
+mod v2 {
+    use variant_counter::*;
+
+    // `Missing` is renamed, `Timeout` is retired and `Teapot` is removed without notice.
+    #[derive(VariantCount)]
+    #[counter(retired = "Timeout", retired = "Gone")]
+    pub enum Status {
+        #[counter(alias = "Missing", alias = "Absent")]
+        NotFound,
+        Ok,
+        Forbidden,
+        #[counter(ignore, track)]
+        Retry,
+    }
+}
+
+fn by_name() -> Encoding {
+    Encoding {
+        by_name: true,
+        ..Encoding::default()
+    }
+}
+
+#[test]
+fn test_load_aliases() {
+    let mut counter = v1::Status::counter();
+    counter.record(&v1::Status::Ok);
+    counter.record(&v1::Status::Missing);
+    counter.record(&v1::Status::Missing);
+    counter.record(&v1::Status::Timeout);
+    counter.record(&v1::Status::Teapot);
+    counter.record(&v1::Status::Teapot);
+    counter.record(&v1::Status::Retry);
+    let bytes = counter.to_bytes_with(by_name());
+
+    let loaded = v2::StatusCounter::load_bytes(&bytes).unwrap();
+    assert_eq!(
+        loaded.counter.aggregate(),
+        v2::StatusCounter::VARIANT_NAMES
+            .iter()
+            .copied()
+            .zip([2, 1, 0])
+            .collect()
+    );
+    assert_eq!(loaded.counter.ignored_aggregate().get("Retry"), Some(&1));
+    assert_eq!(
+        loaded.dropped,
+        vec![
+            Dropped {
+                name: "Timeout".to_string(),
+                count: 1,
+                retired: true,
+            },
+            Dropped {
+                name: "Teapot".to_string(),
+                count: 2,
+                retired: false,
+            },
+        ]
+    );
+    assert_eq!(
+        loaded
+            .unknown()
+            .map(|dropped| &dropped.name[..])
+            .collect::<Vec<_>>(),
+        ["Teapot"]
+    );
+
+    // `from_bytes` drops them silently.
+    let counter = v2::StatusCounter::from_bytes(&bytes).unwrap();
+    assert_eq!(counter.sum(), 3);
+}
+
+#[test]
+fn test_schema() {
+    let schema = v2::StatusCounter::SCHEMA;
+    assert_eq!(schema.hash, v2::StatusCounter::SCHEMA_HASH);
+    assert_eq!(schema.resolve("NotFound"), Some(0));
+    assert_eq!(schema.resolve("Missing"), Some(0));
+    assert_eq!(schema.resolve("Absent"), Some(0));
+    assert_eq!(schema.resolve("Retry"), Some(3));
+    assert_eq!(schema.resolve("Timeout"), None);
+    assert!(schema.is_retired("Timeout"));
+    assert!(!schema.is_retired("Teapot"));
+    assert_eq!(v2::StatusCounter::RETIRED_NAMES, ["Timeout", "Gone"]);
+}
+
+#[test]
+fn test_record_alias() {
+    let mut counter = v2::Status::counter();
+    assert!(counter.record_name("Missing"));
+    assert!(counter.record_name("NotFound"));
+    assert!(!counter.record_name("Timeout"));
+    assert_eq!(counter.sum(), 2);
+}
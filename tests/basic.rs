@@ -11,6 +11,18 @@ enum Level {
     Error,
 }
 
+#[derive(VariantCount)]
+#[counter(derive(PartialOrd, Ord))]
+enum Status {
+    Ok,
+    Err,
+}
+
+#[derive(Default, PartialEq)]
+struct Metrics {
+    levels: LevelCounter,
+}
+
 #[test]
 fn test_basic() {
     assert_eq!(Level::variant_count(), 5);
@@ -38,3 +50,65 @@ fn test_basic() {
     assert_eq!(counter.check_warn(), 0);
     assert_eq!(counter.check_error(), 0);
 }
+
+#[test]
+fn test_erase() {
+    let mut counter = Level::counter();
+    for _ in 0..5 {
+        counter.record(&Level::Warn);
+    }
+
+    counter.erase(&Level::Warn);
+    assert_eq!(counter.check_warn(), 4);
+    counter.erase_n(&Level::Warn, 3);
+    assert_eq!(counter.check_warn(), 1);
+    counter.erase_n(&Level::Warn, 3);
+    assert_eq!(counter.check_warn(), 0);
+
+    counter.record(&Level::Warn);
+    assert_eq!(counter.try_erase(&Level::Warn), Ok(()));
+    let error = counter.try_erase(&Level::Warn).unwrap_err();
+    assert_eq!(
+        error,
+        UnderflowError {
+            name: "Warn",
+            count: 0,
+            requested: 1,
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "cannot erase 1 record(s) of `Warn` which has 0"
+    );
+
+    counter.record(&Level::Info);
+    assert!(counter.try_erase_n(&Level::Info, 2).is_err());
+    assert_eq!(counter.check_info(), 1);
+    assert_eq!(counter.try_erase_n(&Level::Info, 1), Ok(()));
+}
+
+#[test]
+fn test_derives() {
+    let mut metrics = Metrics::default();
+    assert!(metrics == Metrics::default());
+    assert_eq!(LevelCounter::default(), Level::counter());
+
+    metrics.levels.record(&Level::Info);
+    assert!(metrics != Metrics::default());
+
+    let mut other = Level::counter();
+    other.record(&Level::Info);
+    assert_eq!(metrics.levels, other);
+
+    let set = vec![metrics.levels, other, Level::counter()]
+        .into_iter()
+        .collect::<std::collections::HashSet<_>>();
+    assert_eq!(set.len(), 2);
+
+    let mut ok = Status::counter();
+    ok.record(&Status::Ok);
+    let mut err = Status::counter();
+    err.record(&Status::Err);
+    assert!(ok > err);
+    assert_eq!(std::cmp::max(ok, err), ok);
+}
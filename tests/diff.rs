@@ -0,0 +1,100 @@
+#![allow(unused)]
+use variant_counter::{diff::Change, *};
+
+#[derive(VariantCount)]
+enum Status {
+    #[counter(group = "Success")]
+    Ok,
+    #[counter(group = "Error")]
+    NotFound,
+    #[counter(group = "Error")]
+    Timeout,
+    Retry,
+}
+
+fn record(counter: &mut StatusCounter, status: Status, n: usize) {
+    for _ in 0..n {
+        counter.record(&status);
+    }
+}
+
+#[test]
+fn test_diff() {
+    let mut before = Status::counter();
+    record(&mut before, Status::Ok, 4);
+    record(&mut before, Status::NotFound, 2);
+    record(&mut before, Status::Retry, 1);
+    let mut after = Status::counter();
+    record(&mut after, Status::Ok, 6);
+    record(&mut after, Status::NotFound, 2);
+    record(&mut after, Status::Timeout, 3);
+
+    let diff = before.diff(&after);
+    assert!(!diff.is_empty());
+    assert_eq!(diff.variants().len(), 4);
+    let ok = diff.get("Ok").unwrap();
+    assert_eq!(ok.delta(), 2);
+    assert_eq!(ok.relative(), Some(0.5));
+    assert_eq!(diff.get("Timeout").unwrap().relative(), None);
+    assert_eq!(diff.get("Retry").unwrap().delta(), -1);
+    assert_eq!(diff.get("Unknown"), None);
+
+    assert_eq!(
+        diff.changed().map(|change| change.name).collect::<Vec<_>>(),
+        ["Ok", "Timeout", "Retry"]
+    );
+    assert_eq!(
+        diff.appeared()
+            .map(|change| change.name)
+            .collect::<Vec<_>>(),
+        ["Timeout"]
+    );
+    assert_eq!(
+        diff.vanished()
+            .map(|change| change.name)
+            .collect::<Vec<_>>(),
+        ["Retry"]
+    );
+    assert_eq!(
+        diff.total(),
+        Change {
+            name: "total",
+            before: 7,
+            after: 11,
+        }
+    );
+
+    assert_eq!(
+        diff.groups(),
+        [
+            Change {
+                name: "Error",
+                before: 2,
+                after: 5,
+            },
+            Change {
+                name: "Retry",
+                before: 1,
+                after: 0,
+            },
+            Change {
+                name: "Success",
+                before: 4,
+                after: 6,
+            },
+        ]
+    );
+
+    #[cfg(feature = "std")]
+    {
+        assert_eq!(
+            diff.to_string(),
+            "Ok: 4 -> 6 (+2, +50.00%), Timeout: 0 -> 3 (+3, new), Retry: 1 -> 0 (-1, gone)"
+        );
+        assert_eq!(
+            format!("{:#.0}", diff),
+            "Error: 2 -> 5 (+3, +150%), Retry: 1 -> 0 (-1, gone), Success: 4 -> 6 (+2, +50%)"
+        );
+        assert_eq!(before.diff(&before).to_string(), "no change");
+    }
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "full")]
+#![allow(unused)]
+use variant_counter::VariantCount;
+
+#[derive(VariantCount)]
+enum Platform {
+    #[counter(group = "Mobile")]
+    Android,
+    #[counter(group = "Mobile", weight = 2)]
+    #[allow(clippy::upper_case_acronyms)]
+    IOS,
+    #[counter(group = "Desktop")]
+    Linux,
+    Others,
+}
+
+#[test]
+fn test_display() {
+    let mut counter = Platform::counter();
+    counter.record(&Platform::Android);
+    counter.record(&Platform::Android);
+    counter.record(&Platform::Android);
+    counter.record(&Platform::Android);
+    counter.record(&Platform::IOS);
+    counter.record(&Platform::Linux);
+    counter.record(&Platform::Linux);
+
+    assert_eq!(
+        "\
+Android  4   57.14%  ████████
+IOS      1   14.29%  ██
+Linux    2   28.57%  ████
+Others   0    0.00%
+",
+        format!("{:29}", counter)
+    );
+
+    assert_eq!(
+        "\
+Desktop    2   28.57%  ███▎
+  Linux    2   28.57%  ███▎
+Mobile     5   71.43%  ████████
+  Android  4   57.14%  ██████▍
+  IOS      1   14.29%  █▋
+Others     0    0.00%
+",
+        format!("{:#31}", counter)
+    );
+
+    assert_eq!(
+        "\
+Android  4   50.00%  ████████████████████
+IOS      2   25.00%  ██████████
+Linux    2   25.00%  ██████████
+Others   0    0.00%
+",
+        counter.weighted().to_string()
+    );
+}
+
+#[test]
+fn test_display_empty() {
+    assert_eq!(
+        "\
+Android  0    0.00%
+IOS      0    0.00%
+Linux    0    0.00%
+Others   0    0.00%
+",
+        Platform::counter().to_string()
+    );
+}
@@ -1,4 +1,7 @@
 #![cfg(feature = "full")]
+#![allow(dead_code, clippy::enum_variant_names)]
+use std::fmt::Debug;
+
 use variant_counter::*;
 
 #[derive(VariantCount)]
@@ -20,6 +23,23 @@ enum Text<'a, T: Sized> {
     RichText(T),
 }
 
+#[derive(VariantCount)]
+enum Packet<'a, T, const N: usize>
+where
+    T: Clone + Debug,
+{
+    Raw([u8; N]),
+    Borrowed(&'a [T]),
+    Owned { value: T },
+    Empty,
+}
+
+#[derive(VariantCount)]
+enum Fixed<const N: usize> {
+    Full([u8; N]),
+    Partial(usize),
+}
+
 #[test]
 fn test_opt() {
     assert_eq!(Opt::<usize>::variant_count(), 2);
@@ -66,3 +86,42 @@ fn test_text() {
 
     assert_eq!(counter.check_str(), 1);
 }
+
+#[test]
+fn test_lifetime_type_const() {
+    type P<'a> = Packet<'a, String, 4>;
+    assert_eq!(P::variant_count(), 4);
+
+    let owned = String::from("owned");
+    let borrowed = vec![String::from("borrowed")];
+
+    let mut counter = P::counter();
+    counter.record(&P::Raw([0; 4]));
+    counter.record(&P::Borrowed(&borrowed));
+    counter.record(&P::Owned { value: owned });
+    counter.record(&P::Empty);
+    counter.record(&P::Empty);
+    assert_eq!(counter.check_raw(), 1);
+    assert_eq!(counter.check_borrowed(), 1);
+    assert_eq!(counter.check_owned(), 1);
+    assert_eq!(counter.check_empty(), 2);
+
+    // The counter doesn't depend on the generics, so it records any instantiation.
+    counter.record(&Packet::<'_, u8, 16>::Raw([0; 16]));
+    assert_eq!(counter.check_raw(), 2);
+
+    counter.discard(&P::Empty);
+    assert_eq!(counter.check_empty(), 0);
+    counter.reset();
+    assert_eq!(counter.sum(), 0);
+}
+
+#[test]
+fn test_const() {
+    let mut counter = Fixed::<2>::counter();
+    counter.record(&Fixed::Full([1, 2]));
+    counter.record(&Fixed::<8>::Partial(1));
+    assert_eq!(counter.check_full(), 1);
+    assert_eq!(counter.check_partial(), 1);
+    assert_eq!(FixedCounter::default(), Fixed::<8>::counter());
+}
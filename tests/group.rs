@@ -100,3 +100,98 @@ fn test_group_alias() {
         (Some(1), Some(1), Some(2),)
     );
 }
+
+#[test]
+fn test_group_stats() {
+    let mut counter = Platform::counter();
+    counter.record(&Platform::Android);
+    counter.record(&Platform::IOS);
+    counter.record(&Platform::IOS);
+    counter.record(&Platform::Windows);
+    assert_eq!(0.8112781244591328, counter.group_entropy());
+    assert_eq!(0.375, counter.group_gini_impurity());
+
+    let mut other = Platform::counter();
+    other.record(&Platform::Linux);
+    other.record(&Platform::MacOS);
+    other.record(&Platform::ChromeOS);
+    other.record(&Platform::Android);
+    assert_eq!(0.18872187554086717, counter.group_js_divergence(&other));
+    assert_eq!(
+        counter.group_js_divergence(&other),
+        other.group_js_divergence(&counter)
+    );
+    assert_eq!(
+        f64::INFINITY,
+        counter.group_kl_divergence(&Platform::counter())
+    );
+}
+
+#[test]
+fn test_rank() {
+    let mut counter = Platform::counter();
+    counter.record(&Platform::Android);
+    counter.record(&Platform::Android);
+    counter.record(&Platform::Windows);
+    counter.record(&Platform::IOS);
+    counter.record(&Platform::Linux);
+    counter.record(&Platform::Linux);
+    counter.record(&Platform::Linux);
+
+    assert_eq!(
+        vec![("Linux", 3), ("Android", 2), ("IOS", 1)],
+        counter.top_k(3).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![("MacOS", 0), ("ChromeOS", 0)],
+        counter.bottom_k(2).collect::<Vec<_>>()
+    );
+    assert_eq!(7, counter.ranked().count());
+    assert_eq!(
+        vec![("Android", 2), ("IOS", 1), ("Windows", 1), ("Linux", 3)],
+        counter.nonzero().collect::<Vec<_>>()
+    );
+
+    assert_eq!(
+        vec![("desktop", 4), ("mobile", 3), ("Others", 0)],
+        counter.group_ranked().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![("Others", 0)],
+        counter.group_bottom_k(1).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![("desktop", 4)],
+        counter.group_top_k(1).collect::<Vec<_>>()
+    );
+    assert_eq!(2, counter.group_nonzero().count());
+}
+
+#[test]
+fn test_proportions() {
+    let empty = Platform::counter().proportions();
+    assert!(empty.is_empty());
+    assert_eq!(0.0, empty.check_android());
+    assert!(empty.aggregate().values().all(|share| *share == 0.0));
+    assert!(empty.group_aggregate().values().all(|share| *share == 0.0));
+
+    let mut counter = Platform::counter();
+    counter.record(&Platform::Android);
+    counter.record(&Platform::Android);
+    counter.record(&Platform::IOS);
+    counter.record(&Platform::Windows);
+
+    let proportions = counter.proportions();
+    assert_eq!(4, proportions.total());
+    assert_eq!(0.5, proportions.check_android());
+    assert_eq!(Some(&0.25), proportions.aggregate().get("Windows"));
+    assert_eq!(Some(&0.75), proportions.group_aggregate().get("mobile"));
+    assert_eq!(
+        "Android: 50.00%, IOS: 25.00%, Windows: 25.00%, Linux: 0.00%, MacOS: 0.00%, ChromeOS: 0.00%, Others: 0.00%",
+        proportions.to_string()
+    );
+    assert_eq!(
+        "Others: 0.0%, desktop: 25.0%, mobile: 75.0%",
+        format!("{:#.1}", proportions)
+    );
+}
@@ -21,3 +21,75 @@ fn test_ignore() {
 
     counter.record(&Level::Trace);
 }
+
+#[derive(VariantCount)]
+enum Event {
+    #[counter(ignore, track)]
+    Heartbeat,
+    #[counter(ignore, track)]
+    Ping(u8),
+    #[counter(ignore)]
+    Noise,
+    Login,
+    Logout,
+}
+
+#[test]
+fn test_ignore_track() {
+    let mut counter = Event::counter();
+    counter.record(&Event::Heartbeat);
+    counter.record(&Event::Heartbeat);
+    counter.record(&Event::Ping(1));
+    counter.record(&Event::Noise);
+    counter.record(&Event::Login);
+
+    assert_eq!(counter.sum(), 1);
+    assert_eq!(counter.ignored_count(), 3);
+    assert_eq!(counter.aggregate().len(), 2);
+
+    #[cfg(feature = "std")]
+    {
+        let ignored = counter.ignored_aggregate();
+        assert_eq!(ignored.len(), 2);
+        assert_eq!(ignored.get("Heartbeat"), Some(&2));
+        assert_eq!(ignored.get("Ping"), Some(&1));
+    }
+    #[cfg(not(feature = "std"))]
+    assert_eq!(counter.ignored_aggregate(), [("Heartbeat", 2), ("Ping", 1)]);
+
+    counter.erase(&Event::Ping(1));
+    assert_eq!(counter.try_erase(&Event::Ping(1)).unwrap_err().name, "Ping");
+    assert_eq!(counter.try_erase(&Event::Noise), Ok(()));
+    assert_eq!(counter.ignored_count(), 2);
+
+    counter.discard(&Event::Heartbeat);
+    assert_eq!(counter.ignored_count(), 0);
+
+    counter.reset();
+    assert_eq!(counter.ignored_count(), 0);
+    assert_eq!(counter, Event::counter());
+}
+
+#[test]
+fn test_erase_tracked() {
+    let mut counter = Event::counter();
+    counter.record(&Event::Heartbeat);
+    counter.record(&Event::Heartbeat);
+    counter.record(&Event::Login);
+
+    // Tracked ignored variants are erased like other variants.
+    counter.erase_n(&Event::Heartbeat, 1);
+    assert_eq!(counter.ignored_count(), 1);
+    let error = counter.try_erase_n(&Event::Heartbeat, 2).unwrap_err();
+    assert_eq!(
+        (error.name, error.count, error.requested),
+        ("Heartbeat", 1, 2)
+    );
+    assert_eq!(counter.ignored_count(), 1);
+
+    // Untracked ignored variants are skipped.
+    counter.erase(&Event::Noise);
+    assert_eq!(counter.try_erase_n(&Event::Noise, 2), Ok(()));
+    counter.discard(&Event::Noise);
+    assert_eq!((counter.sum(), counter.ignored_count()), (1, 1));
+}
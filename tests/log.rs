@@ -0,0 +1,34 @@
+#![cfg(feature = "log")]
+#![allow(unused)]
+use log::{Level, Log, Record};
+use variant_counter::{
+    logger::{CountingLogger, Discard},
+    *,
+};
+
+fn log(logger: &impl Log, level: Level) {
+    logger.log(
+        &Record::builder()
+            .level(level)
+            .args(format_args!("message"))
+            .build(),
+    );
+}
+
+#[test]
+fn test_counting_logger() {
+    let logger = CountingLogger::new(Discard);
+    let counter = logger.counter();
+    log(&logger, Level::Warn);
+    log(&logger, Level::Warn);
+    log(&logger, Level::Error);
+    log(&logger, Level::Trace);
+
+    let counter = counter.snapshot();
+    assert_eq!(counter.sum(), 4);
+    assert_eq!(counter.ranked().next(), Some(("Warn", 2)));
+    assert_eq!(
+        Level::counter(),
+        variant_counter::builtin::LevelCounter::new()
+    );
+}
@@ -25,4 +25,30 @@ fn test_no_std() {
     let data = counter.group_aggregate();
     assert_eq!(data[0], ("GC", 2));
     assert_eq!(data[1], ("Non-GC", 2));
+
+    counter.record(&Lang::Rust);
+    let mut top = counter.top_k(2);
+    assert_eq!(top.next(), Some(("Rust", 2)));
+    assert_eq!(top.next(), Some(("Cpp", 1)));
+    assert_eq!(top.next(), None);
+    assert_eq!(counter.group_ranked().next(), Some(("Non-GC", 3)));
+
+    let proportions = counter.proportions();
+    assert_eq!(proportions.aggregate()[0], ("Rust", 0.4));
+    assert_eq!(proportions.group_aggregate()[1], ("Non-GC", 0.6));
+    assert_eq!(Lang::counter().proportions().aggregate()[0], ("Rust", 0.0));
+}
+
+static LANGS: LangStaticCounter = LangStaticCounter::new();
+
+#[test]
+fn test_no_std_static() {
+    LANGS.record(&Lang::Rust);
+    LANGS.record(&Lang::Rust);
+    LANGS.record(&Lang::Swift);
+
+    let counter = LANGS.take();
+    assert_eq!(counter.sum(), 3);
+    assert_eq!(counter.group_aggregate()[1], ("Non-GC", 2));
+    assert_eq!(LANGS.snapshot(), Lang::counter());
 }
@@ -0,0 +1,124 @@
+#![allow(unused)]
+use core::cmp::Ordering;
+
+use variant_counter::{
+    pair::{PairCounter, TransitionCounter},
+    *,
+};
+
+#[derive(VariantCount)]
+enum Platform {
+    #[counter(group = "Mobile")]
+    Android,
+    #[counter(group = "Mobile")]
+    Ios,
+    Linux,
+}
+
+#[derive(VariantCount)]
+enum Level {
+    Info,
+    Error,
+    #[counter(ignore)]
+    Trace,
+}
+
+#[test]
+fn test_pair() {
+    let mut pairs = PairCounter::<Platform, Level>::new();
+    pairs.record(&Platform::Android, &Level::Info);
+    pairs.record(&Platform::Android, &Level::Info);
+    pairs.record(&Platform::Ios, &Level::Error);
+    pairs.record(&Platform::Linux, &Level::Error);
+    // Ignored variants are not recorded.
+    pairs.record(&Platform::Linux, &Level::Trace);
+
+    let matrix: &[[usize; 2]; 3] = pairs.matrix();
+    assert_eq!(matrix, &[[2, 0], [0, 1], [0, 1]]);
+    assert_eq!(pairs.get("Android", "Info"), Some(2));
+    assert_eq!(pairs.get("Android", "Trace"), None);
+    assert_eq!(pairs.sum(), 4);
+    assert_eq!(pairs.row_marginals(), [2, 1, 1]);
+    assert_eq!(pairs.column_marginals(), [2, 2]);
+
+    #[cfg(feature = "std")]
+    {
+        assert_eq!(pairs.row_group_marginals(), [("Linux", 1), ("Mobile", 3)]);
+        assert_eq!(pairs.column_group_marginals(), [("Error", 2), ("Info", 2)]);
+        assert_eq!(pairs.group_matrix(), [[1, 0], [1, 2]]);
+        assert_eq!(
+            pairs.to_string(),
+            "        Info Error total\n\
+             Android    2     0     2\n\
+             Ios        0     1     1\n\
+             Linux      0     1     1\n\
+             total      2     2     4"
+        );
+        assert_eq!(
+            format!("{:#}", pairs),
+            "       Error Info total\n\
+             Linux      1    0     1\n\
+             Mobile     1    2     3\n\
+             total      2    2     4"
+        );
+    }
+
+    let copy = pairs;
+    pairs.reset();
+    assert_eq!(pairs.sum(), 0);
+    assert_ne!(pairs, copy);
+}
+
+#[derive(VariantCount)]
+struct Request {
+    authenticated: bool,
+    referrer: Option<String>,
+}
+
+#[test]
+fn test_pair_of_builtin_and_struct() {
+    let mut pairs = PairCounter::<Ordering, Request>::new();
+    pairs.record(
+        &Ordering::Less,
+        &Request {
+            authenticated: true,
+            referrer: Some("https://example.com".to_string()),
+        },
+    );
+    pairs.record(
+        &Ordering::Equal,
+        &Request {
+            authenticated: false,
+            referrer: None,
+        },
+    );
+    assert_eq!(pairs.matrix(), &[[1, 1], [0, 0], [0, 0]]);
+
+    let mut pairs = PairCounter::<Option<u8>, Result<(), ()>>::new();
+    pairs.record(&Some(1), &Err(()));
+    assert_eq!(pairs.get("Some", "Err"), Some(1));
+}
+
+#[test]
+fn test_transition() {
+    let mut transitions = TransitionCounter::<Level>::new();
+    transitions.record_all(&[
+        Level::Info,
+        Level::Info,
+        Level::Trace,
+        Level::Error,
+        Level::Info,
+    ]);
+    // The ignored `Trace` is skipped.
+    assert_eq!(transitions.pairs().matrix(), &[[1, 1], [1, 0]]);
+
+    transitions.restart();
+    transitions.record(&Level::Error);
+    transitions.record(&Level::Error);
+    assert_eq!(transitions.pairs().get("Error", "Error"), Some(1));
+    assert_eq!(transitions.pairs().sum(), 4);
+
+    transitions.reset();
+    transitions.record(&Level::Info);
+    assert_eq!(transitions.pairs().sum(), 0);
+}
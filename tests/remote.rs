@@ -0,0 +1,81 @@
+#![allow(unused)]
+use variant_counter::{builtin::OrderingCounter, *};
+
+mod http {
+    pub enum Method<'a> {
+        Get,
+        Post { body: &'a str },
+        Extension(&'a str),
+        Connect,
+    }
+}
+
+#[derive(VariantCount)]
+#[counter(remote = "http::Method")]
+enum MethodDef<'a> {
+    #[counter(group = "Read")]
+    Get,
+    #[counter(group = "Write")]
+    Post { body: &'a str },
+    #[counter(ignore)]
+    Extension(&'a str),
+    // `Connect` is unknown to the mirror, which is recorded in the other bucket.
+}
+
+#[test]
+fn test_remote() {
+    let mut counter = MethodDef::counter();
+    counter.record(&http::Method::Get);
+    counter.record(&http::Method::Post { body: "{}" });
+    counter.record(&http::Method::Post { body: "" });
+    counter.record(&http::Method::Extension("PURGE"));
+    counter.record(&http::Method::Connect);
+    assert_eq!(counter.sum(), 4);
+    assert_eq!(counter.other_count(), 1);
+    assert_eq!(MethodCounter::VARIANT_NAMES, ["Get", "Post", "__other"]);
+
+    #[cfg(feature = "check")]
+    assert_eq!(counter.check_post(), 2);
+
+    #[cfg(feature = "std")]
+    {
+        let map = counter.group_aggregate();
+        assert_eq!(map.get("Write"), Some(&2));
+        assert_eq!(map.get("__other"), Some(&1));
+        assert_eq!(counter.aggregate().get("__other"), Some(&1));
+    }
+}
+
+#[derive(VariantCount)]
+#[non_exhaustive]
+#[counter(other = "Unknown")]
+pub enum Protocol {
+    Http,
+    Grpc,
+}
+
+#[test]
+fn test_non_exhaustive() {
+    let mut counter = Protocol::counter();
+    counter.record(&Protocol::Http);
+    assert_eq!(counter.other_count(), 0);
+    assert_eq!(ProtocolCounter::VARIANT_NAMES, ["Http", "Grpc", "Unknown"]);
+}
+
+#[test]
+fn test_builtin() {
+    use std::cmp::Ordering;
+
+    let mut counter = Ordering::counter();
+    for (a, b) in [(1, 2), (2, 2), (3, 2), (0, 2)].iter() {
+        counter.record(&a.cmp(b));
+    }
+    assert_eq!(counter.ranked().next(), Some(("Less", 2)));
+    assert_eq!(OrderingCounter::VARIANT_NAMES.len(), 3);
+
+    let mut counter = Result::<u8, String>::counter();
+    counter.record(&Ok::<u8, String>(1));
+    counter.record(&Err::<u8, String>("error".to_owned()));
+    counter.record(&"x".parse::<u8>());
+    assert_eq!(counter.ranked().next(), Some(("Err", 2)));
+}
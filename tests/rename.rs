@@ -0,0 +1,138 @@
+#![cfg(feature = "full")]
+#![allow(unused, clippy::upper_case_acronyms)]
+use variant_counter::VariantCount;
+
+#[derive(VariantCount)]
+enum Platform {
+    ChromeOS,
+    HttpError,
+    IOS,
+    #[counter(rename = "ios_legacy")]
+    Ios,
+    HTTPServer,
+    Variant1,
+    #[counter(rename = "Mac OS", group = "Desktop")]
+    MacOS,
+}
+
+#[derive(VariantCount)]
+#[counter(rename_all = "snake_case")]
+enum Event {
+    #[counter(group = "UserAction")]
+    LogIn,
+    #[counter(group = "UserAction")]
+    LogOut,
+    #[counter(weight = 2)]
+    HTTPError,
+    #[counter(rename = "Custom")]
+    ChromeOS,
+}
+
+#[derive(VariantCount)]
+#[counter(rename_all = "SCREAMING-KEBAB-CASE")]
+enum Level {
+    DebugInfo,
+    Warn,
+}
+
+#[derive(VariantCount)]
+#[counter(rename_all = "camelCase")]
+enum Camel {
+    DebugInfo,
+    Warn,
+}
+
+#[test]
+fn test_method_names() {
+    let mut counter = Platform::counter();
+    counter.record(&Platform::ChromeOS);
+    counter.record(&Platform::HttpError);
+    counter.record(&Platform::IOS);
+    counter.record(&Platform::Ios);
+    counter.record(&Platform::Ios);
+    counter.record(&Platform::HTTPServer);
+    counter.record(&Platform::Variant1);
+    counter.record(&Platform::MacOS);
+
+    assert_eq!(counter.check_chrome_os(), 1);
+    assert_eq!(counter.check_http_error(), 1);
+    assert_eq!(counter.check_ios(), 1);
+    assert_eq!(counter.check_ios_legacy(), 2);
+    assert_eq!(counter.check_http_server(), 1);
+    assert_eq!(counter.check_variant1(), 1);
+    assert_eq!(counter.check_mac_os(), 1);
+
+    counter.erase_ios_legacy();
+    assert_eq!(counter.check_ios_legacy(), 1);
+}
+
+#[test]
+fn test_rename() {
+    let mut counter = Platform::counter();
+    counter.record(&Platform::Ios);
+    counter.record(&Platform::MacOS);
+
+    let map = counter.aggregate();
+    assert_eq!(map.get("ios_legacy"), Some(&1));
+    assert_eq!(map.get("Mac OS"), Some(&1));
+    assert_eq!(map.get("Ios"), None);
+    assert_eq!(map.get("IOS"), Some(&0));
+
+    let group_map = counter.group_aggregate();
+    assert_eq!(group_map.get("Desktop"), Some(&1));
+    assert_eq!(group_map.get("ios_legacy"), Some(&1));
+}
+
+#[test]
+fn test_rename_all() {
+    let mut counter = Event::counter();
+    counter.record(&Event::LogIn);
+    counter.record(&Event::LogOut);
+    counter.record(&Event::HTTPError);
+    counter.record(&Event::ChromeOS);
+
+    // Method names are not affected by `rename_all`.
+    assert_eq!(counter.check_log_in(), 1);
+    assert_eq!(counter.check_http_error(), 1);
+    assert_eq!(counter.check_custom(), 1);
+
+    assert_eq!(
+        EventCounter::VARIANT_NAMES,
+        ["log_in", "log_out", "http_error", "Custom"]
+    );
+
+    let map = counter.aggregate();
+    assert_eq!(map.get("log_in"), Some(&1));
+    assert_eq!(map.get("http_error"), Some(&1));
+    assert_eq!(map.get("Custom"), Some(&1));
+
+    let group_map = counter.group_aggregate();
+    assert_eq!(group_map.len(), 3);
+    assert_eq!(group_map.get("user_action"), Some(&2));
+    assert_eq!(group_map.get("http_error"), Some(&1));
+    assert_eq!(group_map.get("Custom"), Some(&1));
+
+    let weighted = counter.weighted();
+    assert_eq!(weighted.aggregate().get("http_error"), Some(&2));
+    assert_eq!(weighted.group_aggregate().get("user_action"), Some(&2));
+
+    assert_eq!(LevelCounter::VARIANT_NAMES, ["DEBUG-INFO", "WARN"]);
+    assert_eq!(CamelCounter::VARIANT_NAMES, ["debugInfo", "warn"]);
+}
+
+#[test]
+fn test_record_name() {
+    use variant_counter::NamedCounter;
+
+    let mut counter = Event::counter();
+    assert!(counter.record_name("log_in"));
+    assert!(counter.record_name("LogIn"));
+    assert!(counter.record_name("Custom"));
+    assert!(counter.record_name("ChromeOS"));
+    assert!(!counter.record_name("chrome_os"));
+    assert!(!counter.record_name("Unknown"));
+
+    assert_eq!(counter.check_log_in(), 2);
+    assert_eq!(counter.check_custom(), 2);
+    assert_eq!(counter.sum(), 4);
+}
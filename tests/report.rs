@@ -0,0 +1,112 @@
+#![cfg(feature = "std")]
+#![allow(unused)]
+use std::{sync::Arc, time::Duration};
+
+use variant_counter::{
+    report::{ManualClock, Report, Reporter},
+    shared::SharedCounter,
+    *,
+};
+
+#[derive(VariantCount)]
+enum Status {
+    Ok,
+    NotFound,
+    #[counter(ignore, track)]
+    Retry,
+}
+
+#[test]
+fn test_take() {
+    let mut counter = Status::counter();
+    counter.record(&Status::Ok);
+    counter.record(&Status::Retry);
+    let taken = counter.take();
+    assert_eq!(taken.sum(), 1);
+    assert_eq!(taken.ignored_count(), 1);
+    assert_eq!(counter, StatusCounter::new());
+
+    let counter = StatusStaticCounter::new();
+    counter.record(&Status::NotFound);
+    counter.record(&Status::Retry);
+    let taken = counter.take();
+    assert_eq!(taken.sum(), 1);
+    assert_eq!(taken.ignored_count(), 1);
+    assert_eq!(counter.snapshot(), StatusCounter::new());
+
+    let shared = SharedCounter::new(Status::counter());
+    shared.lock().record(&Status::Ok);
+    assert_eq!(shared.take().sum(), 1);
+    assert_eq!(shared.lock().sum(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_take_concurrently() {
+    let counter = StatusShardedCounter::<4>::with_shards();
+    let mut taken = 0;
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..10_000 {
+                    counter.record(&Status::Ok);
+                }
+            });
+        }
+        for _ in 0..100 {
+            taken += counter.take().sum();
+        }
+    });
+    taken += counter.take().sum();
+    // Every record is taken exactly once.
+    assert_eq!(taken, 40_000);
+}
+
+#[test]
+fn test_reporter() {
+    let counter = Arc::new(StatusStaticCounter::new());
+    let clock = ManualClock::new();
+    let mut reports = Vec::new();
+    let mut reporter = Reporter::new(
+        Arc::clone(&counter),
+        &clock,
+        |report: Report<StatusCounter>| reports.push(report),
+        Duration::from_secs(10),
+    );
+
+    counter.record(&Status::Ok);
+    clock.advance(Duration::from_secs(9));
+    assert!(!reporter.tick());
+    clock.advance(Duration::from_secs(1));
+    assert!(reporter.tick());
+    assert!(!reporter.tick());
+
+    // The missed intervals are skipped.
+    counter.record(&Status::NotFound);
+    clock.advance(Duration::from_secs(25));
+    assert!(reporter.tick());
+    clock.advance(Duration::from_secs(5));
+    assert!(reporter.tick());
+
+    let mut ticks = 0;
+    counter.record(&Status::Ok);
+    reporter.run(|| {
+        ticks += 1;
+        ticks <= 2
+    });
+    drop(reporter);
+
+    let spans = reports
+        .iter()
+        .map(|report| (report.start.as_secs(), report.end.as_secs()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        spans,
+        [(0, 10), (10, 35), (35, 40), (40, 50), (50, 60), (60, 60)]
+    );
+    let sums = reports
+        .iter()
+        .map(|report| report.counter.sum())
+        .collect::<Vec<_>>();
+    assert_eq!(sums, [1, 1, 0, 1, 0, 0]);
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "std")]
+#![allow(unused)]
+use variant_counter::{
+    sample::{Rng, Sampling},
+    *,
+};
+
+#[derive(VariantCount)]
+enum Level {
+    Debug,
+    Info,
+    Warn,
+}
+
+fn record<R: Rng>(counter: &mut LevelSampledCounter<R>, level: Level, n: usize) {
+    for _ in 0..n {
+        counter.record(&level);
+    }
+}
+
+#[test]
+fn test_one_in() {
+    let mut counter = LevelSampledCounter::new(Sampling::OneIn(10), 7);
+    record(&mut counter, Level::Debug, 1000);
+    record(&mut counter, Level::Info, 500);
+
+    assert_eq!(counter.sampled().sum(), 150);
+    assert!((counter.sum() - 1500.0).abs() < 1e-9);
+    assert_eq!(counter.aggregate().get("Debug"), Some(&1000.0));
+
+    let [debug, info, warn] = counter.estimates(0.95);
+    assert_eq!(debug.sampled, 100);
+    assert!(debug.contains(1000) && info.contains(500));
+    assert_eq!((warn.estimate, warn.low, warn.high), (0.0, 0.0, 0.0));
+
+    counter.reset();
+    assert_eq!(counter.sampled().sum(), 0);
+}
+
+#[test]
+fn test_probability() {
+    let mut counter = LevelSampledCounter::new(Sampling::Probability(0.01), 42);
+    for _ in 0..100_000 {
+        counter.record(&Level::Debug);
+        counter.record(&Level::Debug);
+        counter.record(&Level::Info);
+    }
+
+    let [debug, info, _] = counter.estimates(0.99);
+    assert!(debug.low < debug.estimate && debug.estimate < debug.high);
+    assert!(debug.contains(200_000), "{:?}", debug);
+    assert!(info.contains(100_000), "{:?}", info);
+
+    // The same seed samples the same values.
+    let mut same = LevelSampledCounter::new(Sampling::Probability(0.01), 42);
+    for _ in 0..100_000 {
+        same.record(&Level::Debug);
+        same.record(&Level::Debug);
+        same.record(&Level::Info);
+    }
+    assert_eq!(same.sampled(), counter.sampled());
+}
+
+#[test]
+fn test_injected_rng() {
+    // A generator of zeros samples the first value of each period.
+    let mut counter = LevelSampledCounter::with_rng(Sampling::OneIn(3), || 0);
+    for level in [Level::Debug, Level::Info, Level::Warn, Level::Warn] {
+        counter.record(&level);
+    }
+    let sampled = counter.estimates(0.95).map(|estimate| estimate.sampled);
+    assert_eq!(sampled, [1, 0, 1]);
+
+    // Every value is sampled with the probability one.
+    let mut counter = LevelSampledCounter::with_rng(Sampling::Probability(1.0), || 0);
+    record(&mut counter, Level::Info, 5);
+    assert_eq!(counter.estimates(0.95)[1].sampled, 5);
+}
+
+#[test]
+#[should_panic(expected = "cannot sample one in zero values")]
+fn test_invalid_sampling() {
+    LevelSampledCounter::new(Sampling::OneIn(0), 0);
+}
+
+mod hygiene {
+    use variant_counter::{sample::Sampling, *};
+
+    // The generated generic parameter must not shadow an enum named `R`.
+    #[derive(VariantCount)]
+    pub enum R {
+        A,
+        B,
+    }
+
+    #[test]
+    fn test_enum_named_r() {
+        let mut counter = RSampledCounter::new(Sampling::OneIn(1), 0);
+        counter.record(&R::A);
+        assert_eq!(counter.sampled().sum(), 1);
+    }
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "std")]
+#![allow(unused)]
+use variant_counter::*;
+
+#[derive(VariantCount)]
+enum Status {
+    Ok,
+    NotFound,
+    #[counter(ignore, track)]
+    Retry,
+    #[counter(ignore)]
+    Unknown,
+}
+
+static STATUS: StatusShardedCounter = StatusShardedCounter::new();
+
+#[test]
+fn test_sharded_counter() {
+    let threads = (0..8)
+        .map(|_| {
+            std::thread::spawn(|| {
+                for _ in 0..1000 {
+                    STATUS.record(&Status::Ok);
+                    STATUS.record(&Status::Retry);
+                    STATUS.record(&Status::Unknown);
+                }
+                STATUS.record(&Status::NotFound);
+            })
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    let counter = STATUS.snapshot();
+    assert_eq!(counter.sum(), 8008);
+    assert_eq!(counter.ignored_count(), 8000);
+    assert_eq!(counter.ranked().next(), Some(("Ok", 8000)));
+
+    STATUS.reset();
+    assert_eq!(STATUS.snapshot(), Status::counter());
+}
+
+#[test]
+fn test_with_shards() {
+    let counter = StatusShardedCounter::<2>::with_shards();
+    counter.record(&Status::NotFound);
+    std::thread::scope(|scope| {
+        scope.spawn(|| counter.record(&Status::NotFound));
+        scope.spawn(|| counter.record(&Status::Ok));
+    });
+    assert_eq!(counter.snapshot().sum(), 3);
+    assert!(std::mem::align_of::<StatusShardedCounter<2>>() >= 128);
+}
+
+#[test]
+#[should_panic(expected = "at least one shard")]
+fn test_zero_shards() {
+    StatusShardedCounter::<0>::with_shards();
+}
+
+mod hygiene {
+    use variant_counter::*;
+
+    // The generated const parameter must not clash with a type in scope.
+    pub struct S;
+
+    #[derive(VariantCount)]
+    pub enum Level {
+        Low,
+        High,
+    }
+
+    #[test]
+    fn test_type_named_s() {
+        let counter = LevelShardedCounter::<2>::with_shards();
+        counter.record(&Level::High);
+        assert_eq!(counter.snapshot().sum(), 1);
+    }
+}
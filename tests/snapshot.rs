@@ -0,0 +1,154 @@
+#![cfg(feature = "std")]
+#![allow(unused)]
+use variant_counter::{
+    snapshot::{Encoding, SnapshotError},
+    *,
+};
+
+mod v1 {
+    use variant_counter::*;
+
+    #[derive(VariantCount)]
+    pub enum Status {
+        Ok,
+        NotFound,
+        Timeout,
+        #[counter(ignore, track)]
+        Retry,
+    }
+}
+
+mod v2 {
+    use variant_counter::*;
+
+    // `Timeout` is removed, `Forbidden` is added and `NotFound` is moved.
+    #[derive(VariantCount)]
+    pub enum Status {
+        NotFound,
+        Ok,
+        Forbidden,
+        #[counter(ignore, track)]
+        Retry,
+    }
+}
+
+fn v1_counter() -> v1::StatusCounter {
+    let mut counter = v1::Status::counter();
+    for _ in 0..300 {
+        counter.record(&v1::Status::Ok);
+    }
+    counter.record(&v1::Status::Timeout);
+    counter.record(&v1::Status::Retry);
+    counter
+}
+
+#[test]
+fn test_snapshot_roundtrip() {
+    let counter = v1_counter();
+
+    let bytes = counter.to_bytes();
+    // The header, `300`, a run of one zero, `1` and `1`.
+    assert_eq!(bytes.len(), 13 + 2 + 2 + 1 + 1);
+    assert_eq!(&bytes[..4], b"VC\x01\x01");
+    assert_eq!(v1::StatusCounter::from_bytes(&bytes), Ok(counter));
+
+    for encoding in [
+        Encoding {
+            zero_run: false,
+            by_name: false,
+        },
+        Encoding {
+            zero_run: false,
+            by_name: true,
+        },
+        Encoding {
+            zero_run: true,
+            by_name: true,
+        },
+    ]
+    .iter()
+    {
+        let bytes = counter.to_bytes_with(*encoding);
+        assert_eq!(v1::StatusCounter::from_bytes(&bytes), Ok(counter));
+    }
+
+    let empty = v1::Status::counter();
+    assert_eq!(empty.to_bytes().len(), 13 + 2);
+    assert_eq!(v1::StatusCounter::from_bytes(&empty.to_bytes()), Ok(empty));
+}
+
+#[test]
+fn test_snapshot_schema() {
+    assert_ne!(
+        v1::StatusCounter::SCHEMA_HASH,
+        v2::StatusCounter::SCHEMA_HASH
+    );
+    assert_eq!(
+        v1::StatusCounter::SCHEMA_HASH,
+        snapshot::schema_hash(&["Ok", "NotFound", "Timeout", "Retry"])
+    );
+
+    let counter = v1_counter();
+    assert_eq!(
+        v2::StatusCounter::from_bytes(&counter.to_bytes()),
+        Err(SnapshotError::SchemaMismatch {
+            expected: v2::StatusCounter::SCHEMA_HASH,
+            found: v1::StatusCounter::SCHEMA_HASH,
+        })
+    );
+
+    // The by-name mode maps the slots by name, `Timeout` is dropped.
+    let bytes = counter.to_bytes_with(Encoding {
+        by_name: true,
+        ..Encoding::default()
+    });
+    let migrated = v2::StatusCounter::from_bytes(&bytes).unwrap();
+    assert_eq!(migrated.sum(), 300);
+    assert_eq!(migrated.ignored_count(), 1);
+    assert_eq!(migrated.ranked().next(), Some(("Ok", 300)));
+}
+
+#[test]
+fn test_snapshot_errors() {
+    let bytes = v1_counter().to_bytes();
+    assert_eq!(
+        v1::StatusCounter::from_bytes(b"JSON{}"),
+        Err(SnapshotError::InvalidMagic)
+    );
+    assert_eq!(
+        v1::StatusCounter::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(SnapshotError::UnexpectedEnd)
+    );
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(
+        v1::StatusCounter::from_bytes(&trailing),
+        Err(SnapshotError::TrailingBytes)
+    );
+
+    let mut version = bytes;
+    version[2] = 2;
+    let error = v1::StatusCounter::from_bytes(&version).unwrap_err();
+    assert_eq!(error, SnapshotError::UnsupportedVersion(2));
+    assert_eq!(error.to_string(), "unsupported snapshot version 2");
+}
+
+#[test]
+fn test_snapshot_by_name_len() {
+    // The header holds the count of names, zero counts are skipped.
+    let mut bytes = v1_counter().to_bytes_with(Encoding {
+        by_name: true,
+        ..Encoding::default()
+    });
+    assert_eq!(bytes[12], 3);
+
+    bytes.extend_from_slice(b"\x02Ok\x01");
+    assert_eq!(
+        v1::StatusCounter::from_bytes(&bytes),
+        Err(SnapshotError::TrailingBytes)
+    );
+
+    bytes[12] = 4;
+    assert_eq!(v1::StatusCounter::from_bytes(&bytes).unwrap().sum(), 302);
+}
@@ -0,0 +1,64 @@
+#![allow(unused)]
+use variant_counter::*;
+
+#[derive(VariantCount)]
+enum ErrorKind {
+    NotFound,
+    Timeout,
+    #[counter(ignore, track)]
+    Interrupted,
+    #[counter(ignore)]
+    Other,
+}
+
+static ERRORS: ErrorKindStaticCounter = ErrorKindStaticCounter::new();
+
+#[test]
+fn test_static_counter() {
+    let threads = (0..4)
+        .map(|_| {
+            std::thread::spawn(|| {
+                for _ in 0..100 {
+                    ERRORS.record(&ErrorKind::NotFound);
+                    ERRORS.record(&ErrorKind::Interrupted);
+                    ERRORS.record(&ErrorKind::Other);
+                }
+                ERRORS.record(&ErrorKind::Timeout);
+            })
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    let counter = ERRORS.snapshot();
+    assert_eq!(counter.sum(), 404);
+    assert_eq!(counter.ignored_count(), 400);
+    assert_eq!(counter.ranked().next(), Some(("NotFound", 400)));
+
+    ERRORS.reset();
+    assert_eq!(ERRORS.snapshot(), ErrorKind::counter());
+}
+
+#[derive(VariantCount)]
+struct Request {
+    cached: bool,
+    user: Option<u32>,
+}
+
+#[test]
+fn test_static_struct_counter() {
+    let counter = RequestStaticCounter::default();
+    counter.record(&Request {
+        cached: true,
+        user: Some(1),
+    });
+    counter.record(&Request {
+        cached: true,
+        user: None,
+    });
+
+    let snapshot = counter.snapshot();
+    assert_eq!(snapshot.sum(), 3);
+    assert_eq!(snapshot.ranked().next(), Some(("cached", 2)));
+}
@@ -16,6 +16,20 @@ enum Rating {
     Loved,
 }
 
+#[derive(VariantCount)]
+enum Backend {
+    A,
+    B,
+    C,
+}
+
+#[derive(VariantCount)]
+enum Rollout {
+    #[counter(expected = 0.1)]
+    Enabled,
+    Disabled,
+}
+
 #[test]
 fn test_stats() {
     let mut counter = Rating::counter();
@@ -46,3 +60,97 @@ fn test_stats() {
     assert_eq!(29.466666666666665, weighted.variance());
     assert_eq!(5.428320796219275, weighted.sd());
 }
+
+#[test]
+fn test_information() {
+    let empty = Rating::counter();
+    assert_eq!(0.0, empty.entropy());
+    assert_eq!(0.0, empty.normalized_entropy());
+    assert_eq!(0.0, empty.gini_impurity());
+    assert_eq!(0.0, empty.kl_divergence(&Rating::counter()));
+    assert_eq!(0.0, empty.js_divergence(&Rating::counter()));
+
+    let mut uniform = Rating::counter();
+    uniform.record(&Rating::Hated);
+    uniform.record(&Rating::Disliked);
+    uniform.record(&Rating::Ok);
+    uniform.record(&Rating::Liked);
+    uniform.record(&Rating::Loved);
+    assert_eq!(1.0, uniform.normalized_entropy());
+    assert_eq!(0.0, uniform.kl_divergence(&uniform));
+    assert_eq!(0.0, uniform.js_divergence(&uniform));
+    assert_eq!(f64::INFINITY, uniform.kl_divergence(&empty));
+    assert_eq!(1.0, uniform.js_divergence(&empty));
+
+    let mut counter = Rating::counter();
+    for _ in 0..2 {
+        counter.record(&Rating::Hated);
+        counter.record(&Rating::Disliked);
+    }
+    for _ in 0..5 {
+        counter.record(&Rating::Ok);
+        counter.record(&Rating::Liked);
+    }
+    assert_eq!(1.863120568566631, counter.entropy());
+    assert_eq!(0.8024023537460198, counter.normalized_entropy());
+    assert_eq!(0.7040816326530612, counter.gini_impurity());
+    assert_eq!(0.4588075263207313, counter.kl_divergence(&uniform));
+    assert_eq!(f64::INFINITY, uniform.kl_divergence(&counter));
+    assert_eq!(0.13931195654170314, counter.js_divergence(&uniform));
+    assert_eq!(
+        counter.js_divergence(&uniform),
+        uniform.js_divergence(&counter)
+    );
+}
+
+#[test]
+fn test_chi_square() {
+    let mut counter = Backend::counter();
+    for _ in 0..30 {
+        counter.record(&Backend::A);
+        counter.record(&Backend::B);
+    }
+    for _ in 0..40 {
+        counter.record(&Backend::C);
+    }
+
+    let result = counter.chi_square_uniform();
+    assert_eq!(result.p_value, counter.chi_square().p_value);
+    assert_eq!(2, result.degrees_of_freedom);
+    assert!((result.statistic - 2.0).abs() < 1e-12);
+    assert!((result.p_value - (-1.0f64).exp()).abs() < 1e-12);
+    assert!(!result.is_significant(0.05));
+    assert_eq!("C", result.most_deviating(1)[0].0);
+    assert_eq!(3, result.most_deviating(10).len());
+
+    let result = counter.chi_square_with(&[3.0, 3.0, 4.0]);
+    assert_eq!(0.0, result.statistic);
+    assert_eq!(1.0, result.p_value);
+
+    assert_eq!(1.0, Backend::counter().chi_square().p_value);
+}
+
+#[test]
+fn test_chi_square_expected() {
+    assert_eq!(["Enabled", "Disabled"], RolloutCounter::VARIANT_NAMES);
+    assert_eq!([0.1, 0.9], RolloutCounter::EXPECTED_PROPORTIONS);
+
+    let mut counter = Rollout::counter();
+    for _ in 0..20 {
+        counter.record(&Rollout::Enabled);
+    }
+    for _ in 0..80 {
+        counter.record(&Rollout::Disabled);
+    }
+
+    let result = counter.chi_square();
+    assert_eq!(1, result.degrees_of_freedom);
+    assert!((result.statistic - 11.11111111111111).abs() < 1e-12);
+    assert!((result.p_value - 0.0008581206663936747).abs() < 1e-9);
+    assert!(result.is_significant(0.05));
+    assert_eq!(
+        ["Enabled", "Disabled"],
+        [result.residuals()[0].0, result.residuals()[1].0]
+    );
+    assert!((counter.chi_square_uniform().statistic - 36.0).abs() < 1e-12);
+}
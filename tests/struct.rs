@@ -0,0 +1,110 @@
+#![allow(unused)]
+use variant_counter::*;
+
+#[derive(VariantCount)]
+struct Request {
+    #[counter(group = "Auth")]
+    authenticated: bool,
+    #[counter(group = "Auth", rename = "admin")]
+    is_admin: bool,
+    referrer: Option<String>,
+    #[counter(weight = 3)]
+    retry: Option<u8>,
+    #[counter(ignore)]
+    path: &'static str,
+}
+
+fn request(
+    authenticated: bool,
+    is_admin: bool,
+    referrer: Option<&str>,
+    retry: Option<u8>,
+) -> Request {
+    Request {
+        authenticated,
+        is_admin,
+        referrer: referrer.map(String::from),
+        retry,
+        path: "/",
+    }
+}
+
+#[test]
+fn test_struct() {
+    let mut counter = Request::counter();
+    counter.record(&request(true, true, None, None));
+    counter.record(&request(true, false, Some("https://example.com"), None));
+    counter.record(&request(false, false, None, Some(1)));
+    counter.record(&request(false, false, None, None));
+
+    assert_eq!(counter.sum(), 5);
+    assert_eq!(
+        RequestCounter::VARIANT_NAMES,
+        ["authenticated", "admin", "referrer", "retry"]
+    );
+
+    #[cfg(feature = "check")]
+    {
+        assert_eq!(counter.check_authenticated(), 2);
+        assert_eq!(counter.check_admin(), 1);
+        assert_eq!(counter.check_referrer(), 1);
+        assert_eq!(counter.check_retry(), 1);
+        assert_eq!(counter.weighted().check_retry(), 3);
+    }
+
+    #[cfg(feature = "std")]
+    {
+        let map = counter.aggregate();
+        assert_eq!(map.get("authenticated"), Some(&2));
+        assert_eq!(map.get("path"), None);
+
+        let group_map = counter.group_aggregate();
+        assert_eq!(group_map.get("Auth"), Some(&3));
+        assert_eq!(group_map.get("retry"), Some(&1));
+    }
+
+    assert_eq!(counter.ranked().next(), Some(("authenticated", 2)));
+
+    // Nothing is erased if one of the fields has no record.
+    assert!(counter
+        .try_erase(&request(true, false, None, Some(1)))
+        .is_ok());
+    assert_eq!(
+        counter
+            .try_erase(&request(true, false, None, Some(1)))
+            .unwrap_err()
+            .name,
+        "retry"
+    );
+    assert_eq!(counter.sum(), 3);
+    counter.erase(&request(true, false, None, Some(1)));
+    assert_eq!(counter.sum(), 2);
+
+    counter.discard(&request(false, true, None, None));
+    assert_eq!(counter.sum(), 1);
+    assert_eq!(counter.proportions().total(), 1);
+}
+
+#[derive(VariantCount)]
+struct Flags<T> {
+    enabled: bool,
+    value: Option<T>,
+}
+
+#[test]
+fn test_generic_struct() {
+    let mut counter = Flags::<u32>::counter();
+    counter.record(&Flags {
+        enabled: true,
+        value: Some(1),
+    });
+    counter.record(&Flags::<u32> {
+        enabled: false,
+        value: None,
+    });
+    assert_eq!(counter.sum(), 2);
+    assert_eq!(Flags::<u32>::variant_count(), 2);
+
+    #[cfg(feature = "stats")]
+    assert!((counter.entropy() - 1.0).abs() < 1e-9);
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "std")]
+#![allow(unused)]
+use variant_counter::{
+    snapshot::Dropped,
+    table::{Format, TableError},
+    *,
+};
+
+#[derive(VariantCount)]
+enum Status {
+    #[counter(group = "Success")]
+    Ok,
+    #[counter(group = "Error", weight = 2)]
+    NotFound,
+    #[counter(group = "Error", rename = "Timeout, \"slow\"")]
+    Timeout,
+    #[counter(ignore, track)]
+    Retry,
+}
+
+fn counter() -> StatusCounter {
+    let mut counter = Status::counter();
+    counter.record(&Status::Ok);
+    counter.record(&Status::Ok);
+    counter.record(&Status::Ok);
+    counter.record(&Status::NotFound);
+    counter.record(&Status::Retry);
+    counter
+}
+
+#[test]
+fn test_write_csv() {
+    let mut csv = Vec::new();
+    counter().write_csv(&mut csv, Format::Csv).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "variant,group,count,weighted,percent\n\
+         Ok,Success,3,3,75.00\n\
+         NotFound,Error,1,2,25.00\n\
+         \"Timeout, \"\"slow\"\"\",Error,0,0,0.00\n"
+    );
+
+    let mut tsv = Vec::new();
+    counter().write_csv(&mut tsv, Format::Tsv).unwrap();
+    assert_eq!(
+        String::from_utf8(tsv).unwrap(),
+        "variant\tgroup\tcount\tweighted\tpercent\n\
+         Ok\tSuccess\t3\t3\t75.00\n\
+         NotFound\tError\t1\t2\t25.00\n\
+         \"Timeout, \"\"slow\"\"\"\tError\t0\t0\t0.00\n"
+    );
+}
+
+#[test]
+fn test_read_csv() {
+    for format in [Format::Csv, Format::Tsv] {
+        let mut bytes = Vec::new();
+        counter().write_csv(&mut bytes, format).unwrap();
+        let loaded = StatusCounter::read_csv(&bytes[..], format).unwrap();
+        assert_eq!(loaded.counter.aggregate(), counter().aggregate());
+        assert!(loaded.dropped.is_empty());
+    }
+
+    // The columns are found by the header, unknown names are dropped.
+    let csv = "count,variant\r\n4,Ok\r\n\r\n2,Retry\r\n5,Gone\r\n";
+    let loaded = StatusCounter::read_csv(csv.as_bytes(), Format::Csv).unwrap();
+    assert_eq!(loaded.counter.aggregate().get("Ok"), Some(&4));
+    assert_eq!(loaded.counter.ignored_aggregate().get("Retry"), Some(&2));
+    assert_eq!(
+        loaded.dropped,
+        [Dropped {
+            name: "Gone".to_string(),
+            count: 5,
+            retired: false,
+        }]
+    );
+}
+
+#[test]
+fn test_table() {
+    let mut before = counter();
+    let mut after = counter();
+    after.record(&Status::Timeout);
+
+    let mut csv = Vec::new();
+    StatusCounter::write_csv_table(&mut csv, Format::Csv, [&before, &after]).unwrap();
+    assert_eq!(
+        String::from_utf8(csv.clone()).unwrap(),
+        "Ok,NotFound,\"Timeout, \"\"slow\"\"\"\n3,1,0\n3,1,1\n"
+    );
+
+    let loaded = StatusCounter::read_csv_table(&csv[..], Format::Csv).unwrap();
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded[0].counter.aggregate(), before.aggregate());
+    assert_eq!(loaded[1].counter.aggregate(), after.aggregate());
+}
+
+#[test]
+fn test_read_error() {
+    assert!(matches!(
+        StatusCounter::read_csv("variant\nOk\n".as_bytes(), Format::Csv),
+        Err(TableError::MissingColumn("count"))
+    ));
+    assert!(matches!(
+        StatusCounter::read_csv("variant,count\nOk\n".as_bytes(), Format::Csv),
+        Err(TableError::ColumnMismatch { line: 2 })
+    ));
+    assert!(matches!(
+        StatusCounter::read_csv_table("Ok\n1\n-1\n".as_bytes(), Format::Csv),
+        Err(TableError::InvalidCount { line: 3, .. })
+    ));
+    assert!(matches!(
+        StatusCounter::read_csv_table("\"Ok\n1\n".as_bytes(), Format::Csv),
+        Err(TableError::UnterminatedQuote { line: 1 })
+    ));
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "tracing")]
+#![allow(unused)]
+use tracing_subscriber::layer::SubscriberExt;
+use variant_counter::{layer::CounterLayer, shared::SharedCounter, *};
+
+#[derive(Debug, VariantCount)]
+enum EventKind {
+    Login,
+    Logout,
+    Ping(u8),
+    #[counter(ignore, track)]
+    Heartbeat,
+}
+
+#[test]
+fn test_tracing_layer() {
+    let layer = CounterLayer::new("kind", EventKind::counter());
+    let counter = layer.counter();
+    let subscriber = tracing_subscriber::registry().with(layer);
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!(kind = ?EventKind::Login, "login");
+        tracing::warn!(kind = ?EventKind::Ping(3));
+        tracing::info!(kind = "Logout");
+        tracing::info!(kind = %"EventKind::Logout");
+        tracing::debug!(kind = ?EventKind::Heartbeat);
+        // Unknown values and other fields are skipped.
+        tracing::info!(kind = "Signup");
+        tracing::info!(kind = %"Login".repeat(100));
+        tracing::info!(other = ?EventKind::Login);
+        tracing::info!("no fields");
+    });
+
+    let counter = counter.snapshot();
+    assert_eq!(counter.sum(), 4);
+    assert_eq!(counter.ignored_count(), 1);
+
+    let map = counter.aggregate();
+    assert_eq!(map.get("Login"), Some(&1));
+    assert_eq!(map.get("Logout"), Some(&2));
+    assert_eq!(map.get("Ping"), Some(&1));
+}
+
+#[test]
+fn test_tracing_shared() {
+    let counter = SharedCounter::new(EventKind::counter());
+    let subscriber = tracing_subscriber::registry()
+        .with(CounterLayer::with_shared("kind", counter.clone()))
+        .with(CounterLayer::with_shared("previous", counter.clone()));
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!(kind = ?EventKind::Logout, previous = ?EventKind::Login);
+    });
+
+    assert_eq!(counter.lock().sum(), 2);
+}
@@ -0,0 +1,21 @@
+use variant_counter::VariantCount;
+
+#[derive(VariantCount)]
+enum Type {
+    #[counter(expected = "half")]
+    Text,
+}
+
+#[derive(VariantCount)]
+enum Level {
+    #[counter(expected = 1.5)]
+    Error,
+}
+
+#[derive(VariantCount)]
+enum Size {
+    #[counter(expected = 1e999)]
+    Large,
+}
+
+fn main() {}
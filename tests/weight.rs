@@ -39,6 +39,31 @@ fn test_weight() {
     assert_eq!(counter.check_error(), 1);
 
     let weighted = counter.weighted();
+    assert_eq!(weighted, counter.clone().weighted());
     assert_eq!(weighted.check_warn(), 5);
     assert_eq!(weighted.check_error(), 10);
 }
+
+#[test]
+fn test_weighted_rank() {
+    let mut counter = Level::counter();
+    counter.record(&Level::Debug);
+    counter.record(&Level::Debug);
+    counter.record(&Level::Debug);
+    counter.record(&Level::Info);
+    counter.record(&Level::Error);
+
+    let weighted = counter.weighted();
+    assert_eq!(
+        vec![("Error", 10), ("Debug", 3), ("Info", 2)],
+        weighted.top_k(3).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![("Trace", 0), ("Warn", 0)],
+        weighted.bottom_k(2).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![("Debug", 3), ("Info", 2), ("Error", 10)],
+        weighted.nonzero().collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,100 @@
+#![allow(unused)]
+use variant_counter::*;
+
+#[derive(PartialEq, PartialOrd)]
+enum Level {
+    Debug,
+    Info,
+    Error,
+}
+
+mod predicate {
+    use super::Level;
+
+    pub fn is_server_error(status: &u16) -> bool {
+        *status >= 500
+    }
+
+    pub fn is_severe(level: &Level, message: &str) -> bool {
+        *level >= Level::Error && !message.is_empty()
+    }
+
+    pub fn never() -> bool {
+        false
+    }
+}
+
+#[derive(VariantCount)]
+enum Event {
+    #[counter(when = "predicate::is_server_error")]
+    Response(u16),
+    #[counter(when = "predicate::is_severe", fallback = "Other")]
+    Log {
+        level: Level,
+        message: &'static str,
+    },
+    #[counter(when = "predicate::never", fallback = "Other")]
+    Tick,
+    Other,
+}
+
+#[test]
+fn test_when() {
+    let mut counter = Event::counter();
+    counter.record(&Event::Response(200));
+    counter.record(&Event::Response(404));
+    counter.record(&Event::Response(503));
+    assert_eq!(counter.sum(), 1);
+
+    counter.record(&Event::Log {
+        level: Level::Error,
+        message: "disk full",
+    });
+    counter.record(&Event::Log {
+        level: Level::Info,
+        message: "started",
+    });
+    counter.record(&Event::Tick);
+    counter.record(&Event::Other);
+
+    #[cfg(feature = "std")]
+    {
+        let map = counter.aggregate();
+        assert_eq!(map.get("Response"), Some(&1));
+        assert_eq!(map.get("Log"), Some(&1));
+        assert_eq!(map.get("Tick"), Some(&0));
+        assert_eq!(map.get("Other"), Some(&3));
+    }
+    #[cfg(not(feature = "std"))]
+    assert_eq!(
+        counter.aggregate(),
+        [("Response", 1), ("Log", 1), ("Tick", 0), ("Other", 3)]
+    );
+
+    // Discard follows the predicate as well.
+    counter.discard(&Event::Response(200));
+    assert_eq!(counter.sum(), 5);
+    counter.discard(&Event::Response(500));
+    assert_eq!(counter.sum(), 4);
+}
+
+#[test]
+fn test_when_fallback_erase() {
+    let mut counter = Event::counter();
+    counter.record(&Event::Other);
+    counter.record(&Event::Other);
+    counter.record(&Event::Tick);
+    assert_eq!(counter.sum(), 3);
+
+    // The failed variant is erased from the fallback variant.
+    counter.erase(&Event::Tick);
+    assert_eq!(counter.sum(), 2);
+    assert_eq!(
+        counter.try_erase_n(&Event::Tick, 3).unwrap_err().name,
+        "Other"
+    );
+
+    // Discarding it clears the fallback variant, including its own records.
+    counter.discard(&Event::Tick);
+    assert_eq!(counter.sum(), 0);
+}